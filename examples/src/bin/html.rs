@@ -1,10 +1,10 @@
 use examples::HtmlBuilder;
 use typed_i18n::TypedI18N;
 use yew::virtual_dom::{VNode, VText};
-use yew::{AttrValue, Html};
+use yew::{html, AttrValue, Html};
 
 #[derive(Copy, Clone, TypedI18N)]
-#[typed_i18n(filename = "demo.yaml", separator = "·")]
+#[typed_i18n(filename = "demo.yaml", separator = "·", markup = "true")]
 #[typed_i18n(
     builder = "HtmlBuilder",
     str_conversion = "as_ref",
@@ -23,4 +23,6 @@ fn main() {
     let name: &String = &"name".to_string();
     let _en = Language::En.hello·you(name, &icon);
     let _de = Language::De.hello·you(name, &icon);
+    let link = |inner: Html| html! { <a href="/terms">{ inner }</a> };
+    let _terms = Language::En.terms(link);
 }
@@ -6,41 +6,71 @@
 
 use proc_macro::TokenStream;
 use proc_macro_error::{abort, proc_macro_error};
+use quote::quote;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use syn::spanned::Spanned;
-use syn::{parse_macro_input, DeriveInput};
-use typed_i18n_support::attribute::Attributes;
-use typed_i18n_support::diagnostic::ProcMacroError;
+use syn::{parse_macro_input, Data, DeriveInput};
+use typed_i18n_support::attribute::{Attributes, ATTRIBUTE_NAME};
+use typed_i18n_support::diagnostic::{ProcMacroError, ProcMacroWarnings};
 use typed_i18n_support::languages::RawLanguages;
+use typed_i18n_support::localize::Localize;
 use typed_i18n_support::messages::Messages;
+use typed_i18n_support::named::NamedCall;
 
 /// Macro to convert a language file and an enum into a type safe i18n system.
 #[proc_macro_error]
 #[proc_macro_derive(TypedI18N, attributes(typed_i18n))]
 pub fn typed_i18n(item: TokenStream) -> TokenStream {
-    let diagnostic = &mut ProcMacroError;
+    let input = parse_macro_input!(item as DeriveInput);
+
+    expand(&input, false).into()
+}
 
+/// Macro to create the enum with the languages of a language file and convert both into a type safe i18n system.
+#[proc_macro_error]
+#[proc_macro]
+pub fn language_enum(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
+
+    expand(&input, true).into()
+}
+
+fn expand(input: &DeriveInput, with_enum: bool) -> proc_macro2::TokenStream {
+    let diagnostic = &mut ProcMacroWarnings::default();
+
     let span = input.span();
     let with_mixed_str = cfg!(feature = "alloc");
-    let attributes = Attributes::parse(diagnostic, span, with_mixed_str, input.attrs);
-
-    let file_path = {
-        let project_root = env::var("CARGO_MANIFEST_DIR_OVERRIDE")
-            .or(env::var("CARGO_MANIFEST_DIR"))
-            .unwrap_or_else(|_| ".".into());
-        let mut path = PathBuf::from(project_root);
-        path.push(&attributes.parameters.filename);
-        path
-    };
+    let attributes = Attributes::parse(diagnostic, span, with_mixed_str, input.attrs.clone());
+
+    let file_path = project_path(&attributes.parameters.filename);
 
     let contents = std::fs::read_to_string(&file_path)
         .unwrap_or_else(|e| abort!(span, format!("Error reading file {file_path:?}: {e}")));
 
-    let languages = RawLanguages::parse(diagnostic, span, input.data);
+    let languages = if with_enum {
+        RawLanguages::parse_with_messages(
+            diagnostic,
+            span,
+            input.data.clone(),
+            &attributes.parameters,
+            &contents,
+        )
+    } else {
+        RawLanguages::parse(diagnostic, span, input.data.clone())
+    };
 
-    let languages = languages.into(diagnostic, span);
+    let mut languages = languages.into(diagnostic, span, &attributes.parameters);
+
+    // the charset files are included to rebuild after a change
+    let mut charset_files = Vec::new();
+    languages.load_charsets(diagnostic, span, &attributes.parameters, |filename| {
+        let path = project_path(filename);
+        let glyphs = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Error reading file {}: {e}", path.display()))?;
+        charset_files.push(relative(&path));
+        Ok(glyphs)
+    });
 
     let messages = Messages::parse(
         diagnostic,
@@ -50,22 +80,99 @@ pub fn typed_i18n(item: TokenStream) -> TokenStream {
         &contents,
     );
 
+    let generated = attributes.generate(
+        diagnostic,
+        &input.vis,
+        &input.ident,
+        &relative(&file_path),
+        &languages,
+        &messages,
+    );
+    let warnings = diagnostic.generate();
+    let generated = quote!(
+        #generated
+        #(const _: &str = ::core::include_str!(#charset_files);)*
+        #warnings
+    );
+
+    if !with_enum {
+        return generated;
+    }
+
+    // the enum without the typed_i18n attributes, the attributes of the listed variants are kept
+    let attrs = input
+        .attrs
+        .iter()
+        .filter(|a| !a.path().is_ident(ATTRIBUTE_NAME));
+    let variants = languages.iter().map(|l| {
+        let ident = &l.ident;
+        let attrs = match &input.data {
+            Data::Enum(data) => data
+                .variants
+                .iter()
+                .find(|v| v.ident == *ident)
+                .map(|v| {
+                    v.attrs
+                        .iter()
+                        .filter(|a| !a.path().is_ident(ATTRIBUTE_NAME))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default(),
+            Data::Struct(_) | Data::Union(_) => Vec::new(),
+        };
+        quote!(#(#attrs)* #ident)
+    });
+    let vis = &input.vis;
+    let ident = &input.ident;
+
+    quote!(
+        #(#attrs)*
+        #vis enum #ident { #(#variants,)* }
+        #generated
+    )
+}
+
+/// The path of a file relative to the crate root.
+fn project_path(filename: &str) -> PathBuf {
+    let project_root = env::var("CARGO_MANIFEST_DIR_OVERRIDE")
+        .or(env::var("CARGO_MANIFEST_DIR"))
+        .unwrap_or_else(|_| ".".into());
+    let mut path = PathBuf::from(project_root);
+    path.push(filename);
+    path
+}
+
+/// The path for `include_str!`.
+fn relative(path: &Path) -> String {
     let current_dir = env::current_dir().expect("Unable to get current directory");
 
-    let relative_path = current_dir
-        .join(file_path)
+    current_dir
+        .join(path)
         .to_str()
         .expect("path contains invalid unicode")
-        .to_string();
+        .to_string()
+}
 
-    attributes
-        .generate(
-            diagnostic,
-            &input.vis,
-            &input.ident,
-            &relative_path,
-            &languages,
-            &messages,
-        )
-        .into()
+/// Macro to localize the values of an enum or a struct with the named macro of a language enum.
+#[proc_macro_error]
+#[proc_macro_derive(Localize, attributes(localize))]
+pub fn localize(item: TokenStream) -> TokenStream {
+    let diagnostic = &mut ProcMacroError;
+
+    let input = parse_macro_input!(item as DeriveInput);
+    let span = input.span();
+
+    Localize::parse(diagnostic, span, input).generate().into()
+}
+
+/// Helper for the generated macro for calls with named arguments.
+#[doc(hidden)]
+#[proc_macro_error]
+#[proc_macro]
+pub fn __named_call(item: TokenStream) -> TokenStream {
+    let diagnostic = &mut ProcMacroError;
+
+    let input = parse_macro_input!(item as NamedCall);
+
+    input.generate(diagnostic).into()
 }
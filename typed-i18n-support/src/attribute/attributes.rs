@@ -1,5 +1,5 @@
 use crate::attribute::parser::Parser;
-use crate::attribute::{Attributes, Builder, Parameters};
+use crate::attribute::{Attributes, Builder, Complete, Fallback, Parameters, Placeholders};
 use crate::diagnostic::Diagnostic;
 use proc_macro2::Span;
 use syn::Attribute;
@@ -44,6 +44,17 @@ impl Attributes {
             filename: "*.*".to_string(),
             separator: "_".to_string(),
             global: None,
+            fallback: Fallback::Listing,
+            named_macro: None,
+            placeholders: Placeholders::Percent,
+            markup: false,
+            negotiate: false,
+            direction: false,
+            param_length: 0,
+            charset: None,
+            charset_file: None,
+            require_complete: Complete::No,
+            empty_is_missing: false,
         });
 
         Attributes {
@@ -1,8 +1,10 @@
+use crate::attribute::parameters::parse_bool;
 use crate::attribute::parser::Parser;
 use crate::attribute::Builder;
 use crate::diagnostic::Diagnostic;
 use proc_macro2::{Ident, Span};
-use syn::Type;
+use syn::parse::Parser as _;
+use syn::{Attribute, Type, Visibility};
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub(crate) enum BuilderVariant {
@@ -70,6 +72,8 @@ impl Builder {
                 syn::parse_str::<Type>("T").unwrap()
             });
             let prefix = parser.remove("prefix").map(|p| p.1);
+            let try_prefix = parser.remove("try_prefix").map(|p| p.1);
+            let prefs_prefix = parser.remove("prefs_prefix").map(|p| p.1);
             let str_conversion =
                 parser
                     .remove("str_conversion")
@@ -107,19 +111,108 @@ impl Builder {
                     }
                 },
             );
+            let bidi_isolation = parse_bool(diagnostic, parser, "bidi_isolation");
+            let include = parser
+                .remove("include")
+                .map_or(Vec::new(), |(_, i)| split_patterns(&i));
+            let exclude = parser
+                .remove("exclude")
+                .map_or(Vec::new(), |(_, e)| split_patterns(&e));
+            let vis = parser.remove("vis").and_then(|(span, v)| {
+                syn::parse_str::<Visibility>(&v)
+                    .map_err(|_| diagnostic.emit_error(span, format!("invalid visibility: {v}")))
+                    .ok()
+            });
+            let attributes = parser.remove("attributes").map_or(Vec::new(), |(span, a)| {
+                Attribute::parse_outer.parse_str(&a).unwrap_or_else(|_| {
+                    diagnostic.emit_error(span, format!("invalid attributes: {a}"));
+                    Vec::new()
+                })
+            });
             parser.finish(diagnostic);
             Some(Builder {
                 span: parser.span(),
                 builder_variant: builder,
                 builder_type,
                 prefix,
+                try_prefix,
+                prefs_prefix,
                 str_conversion,
                 input_ident,
                 input_variant,
                 input_conversion,
+                bidi_isolation,
+                include,
+                exclude,
+                vis,
+                attributes,
             })
         } else {
             None
         }
     }
+
+    /// Whether the message with the `key` and `path` is generated by this builder.
+    ///
+    /// A pattern matches the key or the path joined with `.`.
+    pub(crate) fn is_selected<S: AsRef<str>>(&self, key: &str, path: &[S]) -> bool {
+        let path = path.iter().map(AsRef::as_ref).collect::<Vec<_>>().join(".");
+        let matches =
+            |pattern: &String| glob(pattern, key) || (!path.is_empty() && glob(pattern, &path));
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+/// A list of patterns, separated by whitespace and/or commas.
+fn split_patterns(s: &str) -> Vec<String> {
+    s.split(|s: char| s.is_whitespace() || s == ',')
+        .filter(|s| !s.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Match a glob pattern, `*` matches any number of chars and `?` exactly one.
+fn glob(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // the position after the last `*` and the text position it matched up to
+    let mut star = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            p += 1;
+            star = Some((p, t));
+        } else if let Some((star_p, star_t)) = star {
+            // let the `*` match one more char
+            p = star_p;
+            t = star_t + 1;
+            star = Some((star_p, t));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute::builder::glob;
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob("ui_*", "ui_button"));
+        assert!(glob("ui_*", "ui_"));
+        assert!(!glob("ui_*", "log_ui_button"));
+        assert!(glob("*_title", "page_title"));
+        assert!(glob("a*b*c", "axxbyybc"));
+        assert!(!glob("a*b*c", "axxbyyb"));
+        assert!(glob("h?llo", "hällo"));
+        assert!(!glob("h?llo", "hllo"));
+        assert!(glob("*", ""));
+        assert!(glob("hello", "hello"));
+    }
 }
@@ -1,12 +1,15 @@
 use crate::attribute::builder::{BuilderVariant, InputConversion, InputVariant, StrConversion};
+use crate::charset::Charset;
 use proc_macro2::{Ident, Span};
-use syn::Type;
+use syn::{Attribute, Type, Visibility};
 
 pub(crate) mod attributes;
 pub(crate) mod builder;
 pub(crate) mod parameters;
 pub(crate) mod parser;
 
+pub use parser::ATTRIBUTE_NAME;
+
 pub struct Attributes {
     pub parameters: Parameters,
     pub(crate) builders: Vec<Builder>,
@@ -19,18 +22,51 @@ pub(crate) struct Builder {
     #[allow(clippy::struct_field_names)] // because of a false positive
     pub(crate) builder_type: Type,
     pub(crate) prefix: Option<String>,
+    pub(crate) try_prefix: Option<String>,
+    pub(crate) prefs_prefix: Option<String>,
     pub(crate) str_conversion: StrConversion,
     pub(crate) input_ident: Ident,
     pub(crate) input_variant: InputVariant,
     pub(crate) input_conversion: InputConversion,
+    /// Isolate the str parameters in right to left languages.
+    pub(crate) bidi_isolation: bool,
+    /// Glob patterns of the keys (or paths) of the generated messages, all if empty.
+    pub(crate) include: Vec<String>,
+    /// Glob patterns of the keys (or paths) of the skipped messages.
+    pub(crate) exclude: Vec<String>,
+    /// The visibility of the generated functions, the one of the enum if `None`.
+    pub(crate) vis: Option<Visibility>,
+    /// Attributes of each generated function.
+    pub(crate) attributes: Vec<Attribute>,
 }
 
+#[allow(clippy::struct_excessive_bools)] // the input fields are independent flags
 pub struct Parameters {
     pub span: Span,
     pub filename: String,
     /// Used for joining key parts in serde tree input.
     pub separator: String,
     pub global: Option<Global>,
+    pub fallback: Fallback,
+    /// Name of the generated macro for calls with named arguments.
+    pub named_macro: Option<Ident>,
+    pub placeholders: Placeholders,
+    /// Parse markup tags (`<b>…</b>`).
+    pub markup: bool,
+    /// Generate the function `negotiate`.
+    pub negotiate: bool,
+    /// Generate the function `direction`.
+    pub direction: bool,
+    /// The estimated length of a parameter, used to check the `_max_length` of the messages.
+    pub param_length: usize,
+    /// The chars of all languages without an own charset.
+    pub charset: Option<Charset>,
+    /// A file with the chars (glyphs) of all languages without an own charset, relative to the crate root.
+    pub charset_file: Option<String>,
+    /// Whether the languages without an own setting must have all keys.
+    pub require_complete: Complete,
+    /// Empty texts count as missing keys for the completeness.
+    pub empty_is_missing: bool,
 }
 
 #[derive(Copy, Clone)]
@@ -38,6 +74,37 @@ pub enum Global {
     Atomic,
 }
 
+/// How the fallback chains of the languages are completed.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Fallback {
+    /// All remaining languages in listing order.
+    Listing,
+    /// The parent languages derived from the name (`en_au` → `en`), then all remaining languages in listing order.
+    Hierarchy,
+}
+
+/// Whether a language must have all keys (of the source language).
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Complete {
+    /// Missing keys use the fallback (`false`).
+    No,
+    /// Missing keys are a warning (`warn`).
+    Warn,
+    /// Missing keys are an error (`true`).
+    Yes,
+}
+
+/// The syntax of the parameters in the messages.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Placeholders {
+    /// `%{name}` and `*{name}`.
+    Percent,
+    /// `{name}` and `{name, typed}`.
+    Icu,
+    /// `{{name}}` and `{{name, typed}}`.
+    I18next,
+}
+
 impl Parameters {
     #[must_use]
     pub fn run_new(filename: &str, separator: &str) -> Self {
@@ -46,6 +113,17 @@ impl Parameters {
             filename: filename.to_string(),
             separator: separator.to_string(),
             global: None,
+            fallback: Fallback::Listing,
+            named_macro: None,
+            placeholders: Placeholders::Percent,
+            markup: false,
+            negotiate: false,
+            direction: false,
+            param_length: 0,
+            charset: None,
+            charset_file: None,
+            require_complete: Complete::No,
+            empty_is_missing: false,
         }
     }
 }
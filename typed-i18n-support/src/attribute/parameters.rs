@@ -1,5 +1,6 @@
 use crate::attribute::parser::Parser;
-use crate::attribute::{Global, Parameters};
+use crate::attribute::{Complete, Fallback, Global, Parameters, Placeholders};
+use crate::charset::Charset;
 use crate::diagnostic::Diagnostic;
 use unicode_ident::is_xid_continue;
 
@@ -24,15 +25,114 @@ impl Parameters {
                     None
                 }
             });
+            let fallback = parser
+                .remove("fallback")
+                .map_or(Fallback::Listing, |(sp, fb)| match fb.as_str() {
+                    "listing" => Fallback::Listing,
+                    "hierarchy" => Fallback::Hierarchy,
+                    _ => {
+                        diagnostic.emit_error(sp, "the fallback is not known");
+                        Fallback::Listing
+                    }
+                });
+            let named_macro = parser.remove("named_macro").and_then(|(sp, nm)| {
+                syn::parse_str(&nm)
+                    .map_err(|_| diagnostic.emit_error(sp, format!("invalid macro name: {nm}")))
+                    .ok()
+            });
+            let placeholders =
+                parser
+                    .remove("placeholders")
+                    .map_or(Placeholders::Percent, |(sp, ph)| match ph.as_str() {
+                        "percent" => Placeholders::Percent,
+                        "icu" => Placeholders::Icu,
+                        "i18next" => Placeholders::I18next,
+                        _ => {
+                            diagnostic.emit_error(sp, "the placeholders are not known");
+                            Placeholders::Percent
+                        }
+                    });
+            let markup = parse_bool(diagnostic, parser, "markup");
+            let negotiate = parse_bool(diagnostic, parser, "negotiate");
+            let direction = parse_bool(diagnostic, parser, "direction");
+            let param_length = parser.remove("param_length").map_or(0, |(sp, pl)| {
+                pl.parse().unwrap_or_else(|_| {
+                    diagnostic.emit_error(sp, format!("invalid param_length: {pl}"));
+                    0
+                })
+            });
+            let charset = parse_charset(diagnostic, parser);
+            let charset_file = parser.remove("charset_file").map(|(_, f)| f);
+            let require_complete =
+                parse_complete(diagnostic, parser, "require_complete").unwrap_or(Complete::No);
+            let empty_is_missing = parse_bool(diagnostic, parser, "empty_is_missing");
             parser.finish(diagnostic);
             Some(Parameters {
                 span,
                 filename,
                 separator,
                 global,
+                fallback,
+                named_macro,
+                placeholders,
+                markup,
+                negotiate,
+                direction,
+                param_length,
+                charset,
+                charset_file,
+                require_complete,
+                empty_is_missing,
             })
         } else {
             None
         }
     }
 }
+
+/// The field `charset` of a parser.
+pub(crate) fn parse_charset<D: Diagnostic>(
+    diagnostic: &mut D,
+    parser: &mut Parser,
+) -> Option<Charset> {
+    parser.remove("charset").and_then(|(sp, c)| {
+        Charset::parse(&c)
+            .map_err(|item| diagnostic.emit_error(sp, format!("invalid charset item: {item}")))
+            .ok()
+    })
+}
+
+/// A bool field (`true` or `false`, default `false`) of a parser.
+pub(crate) fn parse_bool<D: Diagnostic>(
+    diagnostic: &mut D,
+    parser: &mut Parser,
+    name: &str,
+) -> bool {
+    parser
+        .remove(name)
+        .map_or(false, |(sp, b)| match b.as_str() {
+            "true" => true,
+            "false" => false,
+            _ => {
+                diagnostic.emit_error(sp, format!("unknown {name} value"));
+                false
+            }
+        })
+}
+
+/// A completeness field (`true`, `warn` or `false`) of a parser.
+pub(crate) fn parse_complete<D: Diagnostic>(
+    diagnostic: &mut D,
+    parser: &mut Parser,
+    name: &str,
+) -> Option<Complete> {
+    parser.remove(name).and_then(|(sp, c)| match c.as_str() {
+        "true" => Some(Complete::Yes),
+        "warn" => Some(Complete::Warn),
+        "false" => Some(Complete::No),
+        _ => {
+            diagnostic.emit_error(sp, format!("unknown {name} value"));
+            None
+        }
+    })
+}
@@ -5,7 +5,8 @@ use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{Attribute, Expr, ExprLit, Lit, Meta, MetaNameValue, Path, PathArguments, Token};
 
-pub(crate) const ATTRIBUTE_NAME: &str = "typed_i18n";
+/// The name of the attributes of the derive macro.
+pub const ATTRIBUTE_NAME: &str = "typed_i18n";
 
 pub(crate) struct Parser {
     span: Span,
@@ -14,7 +15,16 @@ pub(crate) struct Parser {
 
 impl Parser {
     pub(crate) fn parse<D: Diagnostic>(diagnostic: &mut D, a: Attribute) -> Option<Parser> {
-        if !a.path().is_ident(ATTRIBUTE_NAME) {
+        Self::parse_named(diagnostic, a, ATTRIBUTE_NAME)
+    }
+
+    /// Parse an attribute with the name `name`, `None` if it has another name or is invalid.
+    pub(crate) fn parse_named<D: Diagnostic>(
+        diagnostic: &mut D,
+        a: Attribute,
+        name: &str,
+    ) -> Option<Parser> {
+        if !a.path().is_ident(name) {
             return None;
         }
 
@@ -57,6 +67,14 @@ impl Parser {
         }
     }
 
+    /// A parser without values.
+    pub(crate) fn empty(span: Span) -> Parser {
+        Parser {
+            span,
+            values: BTreeMap::new(),
+        }
+    }
+
     pub(crate) fn span(&self) -> Span {
         self.span
     }
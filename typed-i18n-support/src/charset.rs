@@ -0,0 +1,185 @@
+use crate::diagnostic::Diagnostic;
+use crate::languages::Languages;
+use crate::messages::messages::Messages;
+use crate::messages::piece::Piece;
+use crate::messages::transform::Transform;
+use proc_macro2::Span;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// The chars which can be displayed, e.g. the glyphs of a bitmap font.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Charset {
+    /// Inclusive ranges.
+    ranges: Vec<(char, char)>,
+}
+
+impl Charset {
+    /// Parse a space and/or comma separated list of `U+XXXX`, `U+XXXX-U+YYYY`, `ascii` (U+0020-U+007E)
+    /// and `latin1` (`ascii` and U+00A0-U+00FF), the error is the invalid item.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut ranges = Vec::new();
+        for item in s
+            .split(|s: char| s.is_whitespace() || s == ',')
+            .filter(|s| !s.is_empty())
+        {
+            match item {
+                "ascii" => ranges.push((' ', '~')),
+                "latin1" => ranges.extend([(' ', '~'), ('\u{a0}', 'ÿ')]),
+                _ => {
+                    let (first, last) = item.split_once('-').unwrap_or((item, item));
+                    match (code_point(first), code_point(last)) {
+                        (Some(first), Some(last)) if first <= last => ranges.push((first, last)),
+                        _ => return Err(item.to_string()),
+                    }
+                }
+            }
+        }
+        Ok(Charset { ranges })
+    }
+
+    /// All chars of the text, except line breaks.
+    #[must_use]
+    pub fn from_glyphs(s: &str) -> Self {
+        Charset {
+            ranges: s
+                .chars()
+                .filter(|c| *c != '\n' && *c != '\r')
+                .map(|c| (c, c))
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, c: char) -> bool {
+        self.ranges
+            .iter()
+            .any(|(first, last)| (*first..=*last).contains(&c))
+    }
+
+    pub(crate) fn extend(&mut self, other: Charset) {
+        self.ranges.extend(other.ranges);
+    }
+}
+
+/// A code point `U+XXXX`.
+fn code_point(s: &str) -> Option<char> {
+    let hex = s.strip_prefix("U+").or_else(|| s.strip_prefix("u+"))?;
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+impl Messages<'_> {
+    /// Check the texts which each language with a charset displays (with the fallback and
+    /// references).
+    pub(crate) fn check_charsets<D: Diagnostic>(
+        &self,
+        diagnostic: &mut D,
+        span: Span,
+        languages: &Languages,
+    ) {
+        for (k, m) in &self.inner {
+            for l in languages {
+                let charset = if let Some(charset) = &l.charset {
+                    charset
+                } else {
+                    continue;
+                };
+                for (label, pieces) in self.displayed_lines(l, m) {
+                    let mut unsupported = BTreeSet::new();
+                    collect_chars(m.transform, l.turkic(), &pieces, |c| {
+                        if !charset.contains(c) {
+                            unsupported.insert(c);
+                        }
+                    });
+                    if !unsupported.is_empty() {
+                        let mut chars = String::new();
+                        for c in unsupported {
+                            if !chars.is_empty() {
+                                chars.push_str(", ");
+                            }
+                            write!(chars, "{c:?} (U+{:04X})", u32::from(c)).unwrap();
+                        }
+                        diagnostic
+                            .emit_error(span, format!("unsupported chars in {k}.{label}: {chars}"));
+                    }
+                }
+            }
+        }
+    }
+
+    /// The chars of the constant texts which each language displays (with the fallback and references),
+    /// e.g. to create a subset of a font. The parameters are not included.
+    #[must_use]
+    pub fn used_chars(&self, languages: &Languages) -> Vec<(String, BTreeSet<char>)> {
+        let mut result = Vec::new();
+        for l in languages {
+            let mut chars = BTreeSet::new();
+            for (k, m) in &self.inner {
+                if k.starts_with('-') || m.constant_type.is_some() {
+                    // terms are only used in references
+                    continue;
+                }
+                for (_, pieces) in self.displayed_lines(l, m) {
+                    collect_chars(m.transform, l.turkic(), &pieces, |c| {
+                        chars.insert(c);
+                    });
+                }
+            }
+            result.push((l.name.clone(), chars));
+        }
+        result
+    }
+}
+
+/// Call `f` with each char of the constant texts, with the transform applied.
+fn collect_chars<F: FnMut(char)>(
+    transform: Option<Transform>,
+    turkic: bool,
+    pieces: &[Piece<'_>],
+    mut f: F,
+) {
+    let texts;
+    let transformed;
+    let pieces = if let Some(transform) = transform {
+        texts = transform.message_texts(pieces, turkic);
+        transformed = transform.apply_message(pieces, &texts);
+        &transformed
+    } else {
+        pieces
+    };
+    for p in pieces {
+        if let Piece::Text(t) = p {
+            t.chars().for_each(&mut f);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::charset::Charset;
+
+    #[test]
+    fn parse() {
+        let charset = Charset::parse("ascii, U+00E4 U+00F6-U+00FC").unwrap();
+        assert!(charset.contains('a'));
+        assert!(charset.contains('ä'));
+        assert!(charset.contains('ü'));
+        assert!(!charset.contains('ß'));
+        assert!(!charset.contains('\n'));
+        assert!(Charset::parse("latin1").unwrap().contains('ß'));
+        assert_eq!(
+            Charset::parse("U+00FC-U+00E4"),
+            Err("U+00FC-U+00E4".to_string())
+        );
+        assert_eq!(Charset::parse("e4"), Err("e4".to_string()));
+    }
+
+    #[test]
+    fn glyphs() {
+        let charset = Charset::from_glyphs("abc\näöü\r\n");
+        assert!(charset.contains('a'));
+        assert!(charset.contains('ö'));
+        assert!(!charset.contains('d'));
+        assert!(!charset.contains('\n'));
+    }
+}
@@ -1,3 +1,5 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
 use std::fmt::{Debug, Display, Formatter};
 use syn::spanned::Spanned;
 
@@ -6,6 +8,13 @@ pub trait Diagnostic {
     /// Emit an error.
     fn emit_error<S: Spanned, T: Display>(&mut self, span: S, message: T);
 
+    /// Emit a warning.
+    ///
+    /// The default implementation ignores the warning.
+    fn emit_warning<S: Spanned, T: Display>(&mut self, span: S, message: T) {
+        let _ = (span, message);
+    }
+
     /// This should abort (i.e. panic) if an error did occur.
     ///
     /// But maybe not in a test case.
@@ -19,6 +28,7 @@ pub trait Diagnostic {
 /// Use the [`assert`] function to verify the expected result.
 pub struct Simulated {
     aborted: bool,
+    dirty: bool,
     errors: Vec<String>,
 }
 
@@ -29,6 +39,7 @@ impl Simulated {
     pub fn new() -> Self {
         Self {
             aborted: false,
+            dirty: false,
             errors: Vec::new(),
         }
     }
@@ -63,12 +74,20 @@ impl Debug for Simulated {
 impl Diagnostic for Simulated {
     fn emit_error<S: Spanned, T: Display>(&mut self, span: S, message: T) {
         if !self.aborted {
+            self.dirty = true;
             self.errors.push(format!("{:?}: {message}", span.span()));
         }
     }
 
+    fn emit_warning<S: Spanned, T: Display>(&mut self, span: S, message: T) {
+        if !self.aborted {
+            self.errors
+                .push(format!("{:?}: warning: {message}", span.span()));
+        }
+    }
+
     fn should_abort_if_dirty(&mut self) {
-        if !self.errors.is_empty() {
+        if self.dirty {
             self.aborted = true;
         }
     }
@@ -82,7 +101,54 @@ impl Diagnostic for ProcMacroError {
         proc_macro_error::emit_error!(span.span(), message);
     }
 
+    fn emit_warning<S: Spanned, T: Display>(&mut self, span: S, message: T) {
+        // only shown on nightly, see `proc_macro_error`
+        proc_macro_error::emit_warning!(span.span(), message);
+    }
+
     fn should_abort_if_dirty(&mut self) {
         proc_macro_error::abort_if_dirty();
     }
 }
+
+/// Diagnostics with the crate `proc_macro_error`, which also shows the warnings on stable.
+///
+/// The warnings are collected, [`ProcMacroWarnings::generate`] returns a use of a deprecated item
+/// for each, which rustc reports as a warning with the message as the note.
+#[derive(Default)]
+pub struct ProcMacroWarnings {
+    warnings: Vec<(Span, String)>,
+}
+
+impl ProcMacroWarnings {
+    /// The code which triggers the warnings.
+    #[must_use]
+    pub fn generate(&self) -> TokenStream {
+        self.warnings
+            .iter()
+            .map(|(span, message)| {
+                quote_spanned!(*span=>
+                    const _: () = {
+                        #[deprecated(note = #message)]
+                        struct TypedI18nWarning;
+                        let _ = TypedI18nWarning;
+                    };
+                )
+            })
+            .collect()
+    }
+}
+
+impl Diagnostic for ProcMacroWarnings {
+    fn emit_error<S: Spanned, T: Display>(&mut self, span: S, message: T) {
+        ProcMacroError.emit_error(span, message);
+    }
+
+    fn emit_warning<S: Spanned, T: Display>(&mut self, span: S, message: T) {
+        self.warnings.push((span.span(), message.to_string()));
+    }
+
+    fn should_abort_if_dirty(&mut self) {
+        ProcMacroError.should_abort_if_dirty();
+    }
+}
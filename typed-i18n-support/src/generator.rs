@@ -1,18 +1,19 @@
 use crate::attribute::builder::{BuilderVariant, InputConversion, InputVariant, StrConversion};
 use crate::attribute::{Attributes, Builder, Global};
 use crate::diagnostic::Diagnostic;
-use crate::languages::{Language, Languages};
+use crate::languages::{Direction, Language, Languages};
+use crate::messages::compound::Compound;
 use crate::messages::message::Message;
 use crate::messages::message_line::MessageLine;
 use crate::messages::messages::Messages;
 use crate::messages::param_type::ParamType;
 use crate::messages::piece::Piece;
+use crate::messages::transform::Transform;
 use convert_case::{Case, Casing};
-use indexmap::IndexMap;
 use proc_macro2::Ident;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use std::borrow::Cow;
+use std::collections::HashSet;
 use syn::{LitInt, Type, Visibility};
 
 impl Attributes {
@@ -25,6 +26,14 @@ impl Attributes {
         languages: &Languages,
         messages: &Messages,
     ) -> TokenStream {
+        for (k, compound) in &messages.compounds {
+            if compound.struct_ident() == *enum_ident {
+                diagnostic.emit_error(
+                    enum_ident,
+                    format!("the struct name of compound {k} is the name of the enum {enum_ident}"),
+                );
+            }
+        }
         diagnostic.should_abort_if_dirty();
 
         let mut inner = TokenStream::new();
@@ -33,24 +42,86 @@ impl Attributes {
             const _DEPENDENCY: &'static str = include_str!(#relative_path);
         ));
 
+        let mut named_rules = TokenStream::new();
         for builder in &self.builders {
-            builder.generate(diagnostic, vis, enum_ident, languages, messages, &mut inner);
+            builder.generate(
+                diagnostic,
+                vis,
+                enum_ident,
+                languages,
+                messages,
+                &mut inner,
+                &mut named_rules,
+            );
+        }
+
+        for (k, message) in messages {
+            if message.constant_type.is_some() {
+                inner.extend(languages.generate_constant(vis, k, message));
+            }
+        }
+
+        if self.parameters.negotiate {
+            inner.extend(languages.generate_negotiate(vis));
+        }
+        if self.parameters.direction {
+            inner.extend(languages.generate_direction(vis));
+        }
+
+        let mut compounds = TokenStream::new();
+        for compound in messages.compounds.values() {
+            compounds.extend(compound.generate_struct(vis));
         }
 
         let global = self.parameters.global.map_or(TokenStream::new(), |g| {
             g.generate(vis, enum_ident, languages)
         });
 
+        let named_macro = self
+            .parameters
+            .named_macro
+            .as_ref()
+            .map_or(TokenStream::new(), |name| {
+                // a macro can't be exported with `pub use`
+                let use_vis = if let Visibility::Public(_) = vis {
+                    quote!(pub(crate))
+                } else {
+                    quote!(#vis)
+                };
+                quote!(
+                    macro_rules! #name {
+                        #named_rules
+                        ($lang:expr, $f:ident $($rest:tt)*) => {
+                            ::typed_i18n::__named_call!(($lang) $f)
+                        };
+                    }
+                    #[allow(unused_imports)]
+                    #use_vis use #name;
+                )
+            });
+
         quote!(
             impl #enum_ident where #enum_ident : ::std::marker::Copy {
                 #inner
             }
+            #compounds
             #global
+            #named_macro
         )
     }
 }
 
+struct Signature {
+    generics: TokenStream,
+    turbofish: TokenStream,
+    args: TokenStream,
+    arg_names: TokenStream,
+    output: TokenStream,
+    where_clause: TokenStream,
+}
+
 impl Builder {
+    #[allow(clippy::too_many_arguments)]
     fn generate<D: Diagnostic>(
         &self,
         diagnostic: &mut D,
@@ -59,8 +130,11 @@ impl Builder {
         languages: &Languages,
         messages: &Messages,
         output: &mut TokenStream,
+        named_rules: &mut TokenStream,
     ) {
         let prefix = self.prefix.as_deref().unwrap_or("");
+        let vis = self.vis.as_ref().unwrap_or(vis);
+        let attributes = &self.attributes;
 
         if (self.builder_variant == BuilderVariant::StaticStr
             || self.builder_variant == BuilderVariant::MixedStr)
@@ -70,234 +144,880 @@ impl Builder {
 
             return;
         }
-        for (
-            k,
-            Message {
-                params,
-                message_lines,
-                ..
-            },
-        ) in messages
-        {
+        for (k, message) in messages {
+            let params = &message.params;
+            if k.starts_with('-') {
+                // terms are only used in references
+                continue;
+            }
+            if message.constant_type.is_some() {
+                // constants don't depend on the builder
+                continue;
+            }
+            if !self.is_selected(k, &message.path) {
+                continue;
+            }
             if self.builder_variant == BuilderVariant::StaticStr && !params.is_empty() {
                 // non-static result
                 continue;
             }
             if self.input_variant == InputVariant::None
-                && message_lines.iter().any(|(_, m)| {
-                    m.borrow_pieces()
-                        .iter()
-                        .any(|mm| matches!(mm, Piece::Param(_, ParamType::Typed)))
-                })
+                && params.iter().any(|(_, p_type)| *p_type != ParamType::Str)
             {
                 // non-input builder with input message
                 continue;
             }
+            languages.check_moved_params(
+                diagnostic,
+                self.span,
+                messages,
+                (k, message),
+                matches!(
+                    self.input_conversion,
+                    InputConversion::Value | InputConversion::Into
+                ),
+            );
             let fn_ident = Ident::new(&format!("{prefix}{k}"), self.span);
+            let return_static_str = (self.builder_variant == BuilderVariant::StaticStr
+                || self.builder_variant == BuilderVariant::MixedStr)
+                && params.is_empty();
+            let Signature {
+                generics,
+                turbofish,
+                args,
+                arg_names,
+                output: fn_output,
+                where_clause,
+            } = self.signature(params, return_static_str);
+            let fn_output = match message.list_len() {
+                Some(_) if return_static_str => quote!(&'static [&'static str]),
+                Some(len) => quote!([#fn_output; #len]),
+                None => fn_output,
+            };
+
+            let body = languages.generate(self, enum_ident, return_static_str, messages, message);
+            let doc = languages.generate_doc(k, message);
+            let deprecated = message.deprecated.as_deref().map(deprecated_attribute);
             output.extend(quote!(
-                #vis fn #fn_ident
+                #doc
+                #deprecated
+                #(#attributes)*
+                #vis fn #fn_ident #generics (self, #args) -> #fn_output #where_clause {#body}
             ));
-            let mut generics = Vec::new();
-            let mut args = TokenStream::new();
-            args.extend(quote!(self,));
-            for (p_name, p_type) in params {
-                let p_name_ident = Ident::new(p_name, self.span);
-                if *p_type == ParamType::Str {
-                    if self.str_conversion == StrConversion::Ref {
-                        args.extend(quote!(#p_name_ident : &str,));
-                    } else {
-                        let input =
-                            Ident::new(&format!("S{}", generics.len() + 1), Span::call_site());
-                        args.extend(quote!(#p_name_ident : #input,));
-                        generics.push((*p_type, input));
+            named_rules.extend(named_rule(&fn_ident, params));
+            for a in &message.aliases {
+                let alias_ident = Ident::new(&format!("{prefix}{a}"), self.span);
+                let alias = alias_attributes(&fn_ident);
+                output.extend(quote!(
+                    #alias
+                    #(#attributes)*
+                    #vis fn #alias_ident #generics (self, #args) -> #fn_output #where_clause {
+                        self.#fn_ident #turbofish (#arg_names)
                     }
-                } else if self.input_conversion == InputConversion::Ref {
-                    let input_ident = &self.input_ident;
-                    args.extend(quote!(#p_name_ident : &#input_ident,));
-                } else if self.input_conversion == InputConversion::Value {
-                    let input_ident = &self.input_ident;
-                    args.extend(quote!(#p_name_ident : #input_ident,));
+                ));
+                named_rules.extend(named_rule(&alias_ident, params));
+            }
+
+            if let Some(try_prefix) = &self.try_prefix {
+                let try_ident = Ident::new(&format!("{try_prefix}{prefix}{k}"), self.span);
+                let body = languages.generate_try(self, return_static_str, messages, message);
+                output.extend(quote!(
+                    #doc
+                    #deprecated
+                    #(#attributes)*
+                    #vis fn #try_ident #generics (self, #args) -> ::core::option::Option<#fn_output> #where_clause {#body}
+                ));
+                named_rules.extend(named_rule(&try_ident, params));
+                for a in &message.aliases {
+                    let alias_ident = Ident::new(&format!("{try_prefix}{prefix}{a}"), self.span);
+                    let alias = alias_attributes(&try_ident);
+                    output.extend(quote!(
+                        #alias
+                        #(#attributes)*
+                        #vis fn #alias_ident #generics (self, #args) -> ::core::option::Option<#fn_output> #where_clause {
+                            self.#try_ident #turbofish (#arg_names)
+                        }
+                    ));
+                    named_rules.extend(named_rule(&alias_ident, params));
+                }
+            }
+
+            if let Some(prefs_prefix) = &self.prefs_prefix {
+                let prefs_ident = Ident::new(&format!("{prefs_prefix}{prefix}{k}"), self.span);
+                let language = languages.generate_prefs(message);
+                // the function of the language may be deprecated
+                let allow = deprecated.as_ref().map(|_| quote!(#[allow(deprecated)]));
+                output.extend(quote!(
+                    #doc
+                    #deprecated
+                    #allow
+                    #(#attributes)*
+                    #vis fn #prefs_ident #generics (preferences: &[Self], #args) -> #fn_output #where_clause {
+                        #language.#fn_ident #turbofish (#arg_names)
+                    }
+                ));
+                for a in &message.aliases {
+                    let alias_ident = Ident::new(&format!("{prefs_prefix}{prefix}{a}"), self.span);
+                    let alias = alias_attributes(&prefs_ident);
+                    output.extend(quote!(
+                        #alias
+                        #(#attributes)*
+                        #vis fn #alias_ident #generics (preferences: &[Self], #args) -> #fn_output #where_clause {
+                            Self::#prefs_ident #turbofish (preferences, #arg_names)
+                        }
+                    ));
+                }
+            }
+        }
+
+        for (k, compound) in &messages.compounds {
+            if let Some(f) = self.generate_compound(
+                diagnostic, vis, enum_ident, languages, messages, k, compound,
+            ) {
+                output.extend(f);
+                named_rules.extend(named_rule(
+                    &Ident::new(&format!("{prefix}{k}"), self.span),
+                    &compound.params,
+                ));
+            }
+        }
+    }
+
+    /// The function which returns the struct of a compound, `None` if it's skipped.
+    #[allow(clippy::too_many_arguments)]
+    fn generate_compound<D: Diagnostic>(
+        &self,
+        diagnostic: &mut D,
+        vis: &Visibility,
+        enum_ident: &Ident,
+        languages: &Languages,
+        messages: &Messages,
+        k: &str,
+        compound: &Compound,
+    ) -> Option<TokenStream> {
+        let params = &compound.params;
+        if !self.is_selected(k, &compound.path) {
+            return None;
+        }
+        if self.builder_variant == BuilderVariant::StaticStr && !params.is_empty() {
+            // non-static result
+            return None;
+        }
+        if self.input_variant == InputVariant::None
+            && params.iter().any(|(_, p_type)| *p_type != ParamType::Str)
+        {
+            // non-input builder with input message
+            return None;
+        }
+        // a parameter which is moved can only be used once
+        for (p_name, p_type) in params {
+            let moved = *p_type == ParamType::Tag
+                || (*p_type == ParamType::Typed
+                    && matches!(
+                        self.input_conversion,
+                        InputConversion::Value | InputConversion::Into
+                    ));
+            let uses = compound
+                .fields
+                .iter()
+                .filter(|(_, key)| {
+                    messages.inner[key.as_str()]
+                        .params
+                        .iter()
+                        .any(|(p, _)| p == p_name)
+                })
+                .count();
+            if moved && uses > 1 {
+                diagnostic.emit_error(
+                    self.span,
+                    format!("parameter {p_name} is moved and used by more than one message of the compound {k}"),
+                );
+                return None;
+            }
+        }
+
+        let prefix = self.prefix.as_deref().unwrap_or("");
+        let fn_ident = Ident::new(&format!("{prefix}{k}"), self.span);
+        let return_static_str = (self.builder_variant == BuilderVariant::StaticStr
+            || self.builder_variant == BuilderVariant::MixedStr)
+            && params.is_empty();
+        let Signature {
+            generics,
+            args,
+            output: fn_output,
+            where_clause,
+            ..
+        } = self.signature(params, return_static_str);
+        let struct_ident = compound.struct_ident();
+        let mut fields = TokenStream::new();
+        for (field, key) in &compound.fields {
+            let field = Ident::new(field, Span::call_site());
+            let body = languages.generate(
+                self,
+                enum_ident,
+                return_static_str,
+                messages,
+                &messages.inner[key.as_str()],
+            );
+            fields.extend(quote!(#field: {#body},));
+        }
+
+        let doc = Languages::generate_compound_doc(k, compound);
+        let attributes = &self.attributes;
+        Some(quote!(
+            #doc
+            #(#attributes)*
+            #vis fn #fn_ident #generics (self, #args) -> #struct_ident<#fn_output> #where_clause {
+                #struct_ident { #fields }
+            }
+        ))
+    }
+
+    fn signature(&self, params: &[(String, ParamType)], return_static_str: bool) -> Signature {
+        let mut generics = Vec::new();
+        let mut args = TokenStream::new();
+        let mut arg_names = TokenStream::new();
+        for (p_name, p_type) in params {
+            let p_name_ident = Ident::new(p_name, self.span);
+            arg_names.extend(quote!(#p_name_ident,));
+            if *p_type == ParamType::Tag {
+                let input = Ident::new(&format!("F{}", generics.len() + 1), Span::call_site());
+                args.extend(quote!(#p_name_ident : #input,));
+                generics.push((*p_type, input));
+            } else if *p_type == ParamType::Str {
+                if self.str_conversion == StrConversion::Ref {
+                    args.extend(quote!(#p_name_ident : &str,));
                 } else {
-                    let input = Ident::new(&format!("T{}", generics.len() + 1), Span::call_site());
+                    let input = Ident::new(&format!("S{}", generics.len() + 1), Span::call_site());
                     args.extend(quote!(#p_name_ident : #input,));
                     generics.push((*p_type, input));
                 }
-            }
-            if !generics.is_empty() || self.builder_variant == BuilderVariant::Generic {
+            } else if self.input_conversion == InputConversion::Ref {
                 let input_ident = &self.input_ident;
-                output.extend(quote!(<));
-                if self.builder_variant == BuilderVariant::Generic {
-                    output.extend(quote!(T,));
-                }
-                if self.input_variant == InputVariant::Generic
-                    && generics.iter().any(|(t, _)| *t == ParamType::Typed)
-                {
-                    output.extend(quote!(#input_ident,));
-                }
-                for (_, g) in &generics {
-                    output.extend(quote!(#g,));
-                }
-                output.extend(quote!(>));
-            }
-            output.extend(quote!((#args)));
-            let return_static_str = (self.builder_variant == BuilderVariant::StaticStr
-                || self.builder_variant == BuilderVariant::MixedStr)
-                && params.is_empty();
-            let builder_type = &self.builder_type;
-            if return_static_str {
-                output.extend(quote!( -> &'static str ));
-            } else if self.builder_variant == BuilderVariant::MixedStr {
-                output.extend(quote!( -> String ));
-            } else if self.builder_variant == BuilderVariant::Generic {
-                output.extend(quote!( -> T::Output ));
+                args.extend(quote!(#p_name_ident : &#input_ident,));
+            } else if self.input_conversion == InputConversion::Value {
+                let input_ident = &self.input_ident;
+                args.extend(quote!(#p_name_ident : #input_ident,));
             } else {
-                output.extend(quote!( -> <#builder_type as ::typed_i18n::Builder>::Output ));
+                let input = Ident::new(&format!("T{}", generics.len() + 1), Span::call_site());
+                args.extend(quote!(#p_name_ident : #input,));
+                generics.push((*p_type, input));
             }
-            if !generics.is_empty() {
-                let input_ident = &self.input_ident;
-                output.extend(quote!(where));
-                if generics.iter().any(|(t, _)| *t == ParamType::Typed) {
-                    if self.input_conversion == InputConversion::AsRef {
-                        output.extend(
-                            quote!(#builder_type : ::typed_i18n::BuilderFromRef<#input_ident>,),
-                        );
-                    } else {
-                        output.extend(
-                            quote!(#builder_type : ::typed_i18n::BuilderFromValue<#input_ident>,),
-                        );
-                    }
+        }
+        let mut generic_params = TokenStream::new();
+        if !generics.is_empty() || self.builder_variant == BuilderVariant::Generic {
+            let input_ident = &self.input_ident;
+            if self.builder_variant == BuilderVariant::Generic {
+                generic_params.extend(quote!(T,));
+            }
+            if self.input_variant == InputVariant::Generic
+                && generics.iter().any(|(t, _)| *t != ParamType::Str)
+            {
+                generic_params.extend(quote!(#input_ident,));
+            }
+            for (_, g) in &generics {
+                generic_params.extend(quote!(#g,));
+            }
+        }
+        let builder_type = &self.builder_type;
+        let output = if return_static_str {
+            quote!(&'static str)
+        } else if self.builder_variant == BuilderVariant::MixedStr {
+            quote!(String)
+        } else if self.builder_variant == BuilderVariant::Generic {
+            quote!(T::Output)
+        } else {
+            quote!(<#builder_type as ::typed_i18n::Builder>::Output)
+        };
+        let mut where_clause = TokenStream::new();
+        // the typed parameters with the conversions `value` and `ref` are not generic, but still
+        // require a bound for a generic builder
+        let from_input = generics.iter().any(|(t, _)| *t != ParamType::Str)
+            || (self.builder_variant == BuilderVariant::Generic
+                && params.iter().any(|(_, t)| *t == ParamType::Typed));
+        if !generics.is_empty() || from_input {
+            let input_ident = &self.input_ident;
+            where_clause.extend(quote!(where));
+            if from_input {
+                if matches!(
+                    self.input_conversion,
+                    InputConversion::AsRef | InputConversion::Ref
+                ) {
+                    where_clause.extend(
+                        quote!(#builder_type : ::typed_i18n::BuilderFromRef<#input_ident>,),
+                    );
+                } else {
+                    where_clause.extend(
+                        quote!(#builder_type : ::typed_i18n::BuilderFromValue<#input_ident>,),
+                    );
                 }
-                for (t, g) in &generics {
-                    match *t {
-                        ParamType::Str => output.extend(quote!(#g : ::core::convert::AsRef<str>,)),
-                        ParamType::Typed => match self.input_conversion {
-                            InputConversion::Into => {
-                                output.extend(quote!(#g : ::core::convert::Into<#input_ident>,));
-                            }
-                            InputConversion::AsRef => {
-                                output.extend(quote!(#g : ::core::convert::AsRef<#input_ident>,));
-                            }
-                            InputConversion::Value | InputConversion::Ref => {}
-                        },
+            }
+            for (t, g) in &generics {
+                match *t {
+                    ParamType::Str => {
+                        where_clause.extend(quote!(#g : ::core::convert::AsRef<str>,));
+                    }
+                    ParamType::Typed => match self.input_conversion {
+                        InputConversion::Into => {
+                            where_clause.extend(quote!(#g : ::core::convert::Into<#input_ident>,));
+                        }
+                        InputConversion::AsRef => {
+                            where_clause.extend(quote!(#g : ::core::convert::AsRef<#input_ident>,));
+                        }
+                        InputConversion::Value | InputConversion::Ref => {}
+                    },
+                    ParamType::Tag => {
+                        where_clause.extend(quote!(#g : ::core::ops::FnOnce(<#builder_type as ::typed_i18n::Builder>::Output) -> #input_ident,));
                     }
                 }
-            } else if self.builder_variant == BuilderVariant::Generic {
-                output.extend(quote!(where #builder_type : ::typed_i18n::Builder,));
             }
-            let body = languages.generate(
-                builder_type,
-                enum_ident,
-                return_static_str,
-                self.str_conversion,
-                self.input_conversion,
-                message_lines,
-            );
-            output.extend(quote!({#body}));
+        } else if self.builder_variant == BuilderVariant::Generic {
+            where_clause.extend(quote!(where #builder_type : ::typed_i18n::Builder,));
+        }
+
+        let (generics, turbofish) = if generic_params.is_empty() {
+            (TokenStream::new(), TokenStream::new())
+        } else {
+            (quote!(<#generic_params>), quote!(::<#generic_params>))
+        };
+        Signature {
+            generics,
+            turbofish,
+            args,
+            arg_names,
+            output,
+            where_clause,
         }
     }
 }
 
+/// The attribute of a deprecated message, the note is omitted if it's empty.
+fn deprecated_attribute(note: &str) -> TokenStream {
+    if note.is_empty() {
+        quote!(#[deprecated])
+    } else {
+        quote!(#[deprecated(note = #note)])
+    }
+}
+
+/// The attributes of an alias, which forwards to the function `target`.
+fn alias_attributes(target: &Ident) -> TokenStream {
+    let doc = format!(" Alias of [`Self::{target}`].");
+    let note = format!("use {target}");
+    quote!(
+        #[doc = #doc]
+        #[deprecated(note = #note)]
+        // the target may be deprecated
+        #[allow(deprecated)]
+    )
+}
+
+/// The rule for the named macro to call the function.
+fn named_rule(fn_ident: &Ident, params: &[(String, ParamType)]) -> TokenStream {
+    let params = params.iter().map(|(p, _)| Ident::new(p, Span::call_site()));
+    quote!(
+        ($lang:expr, #fn_ident $(::<$($g:ty),* $(,)?>)? $(, $($args:tt)*)?) => {
+            ::typed_i18n::__named_call!(($lang) #fn_ident ($($($g),*)?) [#(#params)*] $($($args)*)?)
+        };
+    )
+}
+
 impl Languages {
     fn generate(
         &self,
-        builder_type: &Type,
+        builder: &Builder,
         enum_ident: &Ident,
         return_static_str: bool,
-        str_conversion: StrConversion,
-        input_conversion: InputConversion,
-        m: &IndexMap<Cow<'_, str>, MessageLine<'_>>,
+        messages: &Messages,
+        message: &Message,
     ) -> TokenStream {
-        if m.len() == 1 {
-            return self.iter().next().expect("no language!").generate(
-                builder_type,
-                return_static_str,
-                str_conversion,
-                input_conversion,
-                m,
-            );
+        let has_references = message.lines().iter().any(|(_, ml)| {
+            ml.borrow_pieces()
+                .iter()
+                .any(|p| matches!(p, Piece::Ref(_)))
+        });
+        let first = self.iter().next().expect("no language!");
+        let same_mapping = self
+            .iter()
+            .all(|l| l.isolate(builder) == first.isolate(builder) && l.turkic() == first.turkic());
+        let num_languages = message.message_lines.len() + message.lists.len();
+        if num_languages == 1 && !has_references && same_mapping {
+            return first.generate(builder, return_static_str, messages, message);
         }
 
         let mut body = TokenStream::new();
         for l in self {
-            let gl = l.generate(
-                builder_type,
-                return_static_str,
-                str_conversion,
-                input_conversion,
-                m,
-            );
+            let gl = l.generate(builder, return_static_str, messages, message);
             let lang_ident = &l.ident;
             body.extend(quote!(#enum_ident :: #lang_ident => {#gl},));
         }
         quote!(match self { #body })
     }
+
+    fn generate_try(
+        &self,
+        builder: &Builder,
+        return_static_str: bool,
+        messages: &Messages,
+        message: &Message,
+    ) -> TokenStream {
+        let mut body = TokenStream::new();
+        for l in self {
+            let lang_ident = &l.ident;
+            if message.has_language(&l.name) {
+                let gl =
+                    l.generate_language(builder, return_static_str, messages, message, &l.name);
+                body.extend(quote!(Self::#lang_ident => ::core::option::Option::Some(#gl),));
+            } else {
+                body.extend(quote!(Self::#lang_ident => ::core::option::Option::None,));
+            }
+        }
+        quote!(match self { #body })
+    }
+
+    /// The expression to select the language from `preferences`.
+    fn generate_prefs(&self, message: &Message) -> TokenStream {
+        let default_ident = &self.default_language().ident;
+        let first = quote!(preferences.first().copied().unwrap_or(Self::#default_ident));
+        if self.iter().all(|l| message.has_language(&l.name)) {
+            return first;
+        }
+        let mut body = TokenStream::new();
+        for l in self {
+            let lang_ident = &l.ident;
+            let has_text = message.has_language(&l.name);
+            body.extend(quote!(Self::#lang_ident => #has_text,));
+        }
+        quote!(
+            ::core::iter::Iterator::find(&mut preferences.iter(), |language| match language { #body })
+                .copied()
+                .unwrap_or_else(|| #first)
+        )
+    }
+}
+
+impl Languages {
+    /// The documentation of the function of a message: key, path, parameters and the text of each language.
+    fn generate_doc(&self, k: &str, message: &Message) -> TokenStream {
+        let mut doc = vec![format!(" Message {}.", code(k))];
+        let path = message.path.join(".");
+        if !path.is_empty() && path != k {
+            doc.push(format!(" Path {}.", code(&path)));
+        }
+        let metadata = &message.metadata;
+        if let Some(description) = &metadata.description {
+            doc.push(String::new());
+            doc.extend(description.lines().map(|l| format!(" {l}")));
+        }
+        if let Some(note) = &metadata.note {
+            doc.push(String::new());
+            let mut lines = note.lines();
+            doc.push(format!(" Note: {}", lines.next().unwrap_or_default()));
+            doc.extend(lines.map(|l| format!(" {l}")));
+        }
+        if !metadata.tags.is_empty() {
+            let tags = metadata.tags.iter().map(|t| code(t)).collect::<Vec<_>>();
+            doc.push(String::new());
+            doc.push(format!(" Tags: {}.", tags.join(", ")));
+        }
+        if let Some(max_length) = metadata.max_length {
+            doc.push(String::new());
+            doc.push(format!(
+                " Maximum length: {} {}.",
+                max_length.length, max_length.unit
+            ));
+        }
+        if !message.params.is_empty() {
+            doc.push(String::new());
+            doc.push(" Parameters:".to_string());
+            for (p_name, p_type) in &message.params {
+                let p_type = match p_type {
+                    ParamType::Str => "str",
+                    ParamType::Typed => "input",
+                    ParamType::Tag => "tag",
+                };
+                doc.push(format!(" * {}: {p_type}", code(p_name)));
+            }
+        }
+        doc.push(String::new());
+        doc.push(" Texts:".to_string());
+        for l in self {
+            let lang = l
+                .fallback
+                .iter()
+                .find(|f| message.has_language(f) || message.constants.contains_key(f.as_str()))
+                .expect("couldn't find a value");
+            let text = if let Some(list) = message.lists.get(lang.as_str()) {
+                let items = list
+                    .iter()
+                    .map(|ml| code(ml.borrow_line()))
+                    .collect::<Vec<_>>();
+                format!("[{}]", items.join(", "))
+            } else if let Some(ml) = message.message_lines.get(lang.as_str()) {
+                code(ml.borrow_line())
+            } else if let Some(constant) = message.constants.get(lang.as_str()) {
+                code(&constant.to_string())
+            } else {
+                continue;
+            };
+            if *lang == l.name {
+                doc.push(format!(" * {}: {text}", code(&l.name)));
+            } else {
+                doc.push(format!(
+                    " * {} (fallback {}): {text}",
+                    code(&l.name),
+                    code(lang)
+                ));
+            }
+        }
+        quote!(#(#[doc = #doc])*)
+    }
+
+    /// The documentation of the function of a compound: key and the keys of its messages.
+    fn generate_compound_doc(k: &str, compound: &Compound) -> TokenStream {
+        let mut doc = vec![format!(" Compound {}.", code(k)), String::new()];
+        doc.push(" Fields:".to_string());
+        for (field, key) in &compound.fields {
+            doc.push(format!(" * {}: message {}", code(field), code(key)));
+        }
+        quote!(#(#[doc = #doc])*)
+    }
+}
+
+/// Inline code in markdown, with a fence which is longer than any backtick run in the text.
+fn code(text: &str) -> String {
+    let text = text.replace('\n', "\\n");
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        if c == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    let fence = "`".repeat(longest + 1);
+    if longest > 0 || text.starts_with(' ') || text.ends_with(' ') {
+        format!("{fence} {text} {fence}")
+    } else {
+        format!("{fence}{text}{fence}")
+    }
+}
+
+impl Languages {
+    /// Check that the texts (with the references resolved) use each moved parameter only once,
+    /// the tags are always moved and the typed parameters if `moves_typed`.
+    fn check_moved_params<D: Diagnostic>(
+        &self,
+        diagnostic: &mut D,
+        span: Span,
+        messages: &Messages,
+        (k, message): (&str, &Message),
+        moves_typed: bool,
+    ) {
+        for l in self {
+            let lang = l
+                .fallback
+                .iter()
+                .find(|f| message.has_language(f))
+                .expect("couldn't find a value");
+            let lines = message.lists.get(lang.as_str()).map_or_else(
+                || {
+                    message
+                        .message_lines
+                        .get(lang.as_str())
+                        .into_iter()
+                        .collect()
+                },
+                |list| list.iter().collect::<Vec<_>>(),
+            );
+            for ml in lines {
+                let mut pieces = Vec::new();
+                if !messages.flatten(&l.fallback, ml.borrow_pieces(), &mut pieces) {
+                    continue;
+                }
+                let mut used = HashSet::new();
+                for p in pieces {
+                    let p_name = match p {
+                        Piece::Param(p_name, ParamType::Typed, _) if moves_typed => p_name,
+                        Piece::TagOpen(p_name) => p_name,
+                        _ => continue,
+                    };
+                    if !used.insert(p_name) {
+                        diagnostic.emit_error(
+                            span,
+                            format!(
+                                r#"duplicate use of a typed parameter: "{p_name}" in key {k}.{} (through a reference)"#,
+                                l.name
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn generate_negotiate(&self, vis: &Visibility) -> TokenStream {
+        let mut tags = TokenStream::new();
+        for l in self {
+            if let Some(tag) = &l.tag {
+                let language_ident = &l.ident;
+                tags.extend(quote!((#tag, Self::#language_ident),));
+            }
+        }
+        let default_ident = &self.default_language().ident;
+
+        quote! {
+            #vis fn negotiate(preferences: &str) -> Self {
+                ::typed_i18n::negotiate(preferences, &[#tags]).unwrap_or(Self::#default_ident)
+            }
+        }
+    }
+
+    /// The accessor of a constant, the value of each language is taken from its fallback.
+    fn generate_constant(&self, vis: &Visibility, k: &str, message: &Message) -> TokenStream {
+        let constant_type = message.constant_type.as_deref().unwrap_or_default();
+        let ty = syn::parse_str::<Type>(constant_type).expect("invalid type");
+        let mut body = TokenStream::new();
+        for l in self {
+            let language_ident = &l.ident;
+            let value = l
+                .fallback
+                .iter()
+                .find_map(|f| message.constants.get(f.as_str()))
+                .expect("couldn't find a value")
+                .generate(&ty);
+            body.extend(quote!(Self::#language_ident => #value,));
+        }
+        let fn_ident = Ident::new(k, Span::call_site());
+        let doc = self.generate_doc(k, message);
+        let deprecated = message.deprecated.as_deref().map(deprecated_attribute);
+        let aliases = message.aliases.iter().map(|a| {
+            let alias_ident = Ident::new(a, Span::call_site());
+            let alias = alias_attributes(&fn_ident);
+            quote!(
+                #alias
+                #vis fn #alias_ident(self) -> #ty {
+                    self.#fn_ident()
+                }
+            )
+        });
+
+        quote! {
+            #doc
+            #deprecated
+            #vis fn #fn_ident(self) -> #ty {
+                match self { #body }
+            }
+            #(#aliases)*
+        }
+    }
+
+    fn generate_direction(&self, vis: &Visibility) -> TokenStream {
+        let mut body = TokenStream::new();
+        for l in self {
+            let language_ident = &l.ident;
+            let direction = match l.direction {
+                Direction::Ltr => quote!(Ltr),
+                Direction::Rtl => quote!(Rtl),
+            };
+            body.extend(quote!(Self::#language_ident => ::typed_i18n::Direction::#direction,));
+        }
+
+        quote! {
+            #vis fn direction(self) -> ::typed_i18n::Direction {
+                match self { #body }
+            }
+        }
+    }
 }
 
 impl Language {
     fn generate(
         &self,
-        builder_type: &Type,
+        builder: &Builder,
         return_static_str: bool,
-        str_conversion: StrConversion,
-        input_conversion: InputConversion,
-        m: &IndexMap<Cow<'_, str>, MessageLine<'_>>,
+        messages: &Messages,
+        message: &Message,
     ) -> TokenStream {
-        let m = self
+        let lang = self
             .fallback
             .iter()
-            .find_map(|l| m.get(l.as_str()))
-            .expect("couldn't find a value")
-            .borrow_pieces()
-            .as_slice();
-
-        if return_static_str {
-            return match m.first() {
-                None => quote!(""),
-                Some(Piece::Text(t)) => quote!(#t),
-                _ => panic!("invalid return_static_str"),
-            };
+            .find(|l| message.has_language(l))
+            .expect("couldn't find a value");
+
+        self.generate_language(builder, return_static_str, messages, message, lang)
+    }
+
+    /// The text or list of `lang`, the references are resolved with the fallback of this language.
+    fn generate_language(
+        &self,
+        builder: &Builder,
+        return_static_str: bool,
+        messages: &Messages,
+        message: &Message,
+        lang: &str,
+    ) -> TokenStream {
+        if let Some(list) = message.lists.get(lang) {
+            let items = list.iter().map(|ml| {
+                self.generate_line(builder, return_static_str, messages, message.transform, ml)
+            });
+            if return_static_str {
+                quote!(&[#(#items),*])
+            } else {
+                quote!([#(#items),*])
+            }
+        } else {
+            let ml = message
+                .message_lines
+                .get(lang)
+                .expect("couldn't find a value");
+            self.generate_line(builder, return_static_str, messages, message.transform, ml)
         }
-        if let &[Piece::Text(t)] = &m {
-            return quote!(<#builder_type as ::typed_i18n::Builder>::const_str(#t));
+    }
+
+    fn generate_line(
+        &self,
+        builder: &Builder,
+        return_static_str: bool,
+        messages: &Messages,
+        transform: Option<Transform>,
+        ml: &MessageLine<'_>,
+    ) -> TokenStream {
+        let mut pieces = Vec::new();
+        let valid = messages.flatten(&self.fallback, ml.borrow_pieces(), &mut pieces);
+        assert!(valid, "invalid reference");
+        if let Some(transform) = transform {
+            let texts = transform.message_texts(&pieces, self.turkic());
+            let pieces = transform.apply_message(&pieces, &texts);
+            generate_pieces(builder, self, return_static_str, &pieces)
+        } else {
+            generate_pieces(builder, self, return_static_str, &pieces)
         }
-        if m.is_empty() {
-            return quote!(<#builder_type as ::typed_i18n::Builder>::empty());
+    }
+
+    /// Whether the str parameters are isolated.
+    fn isolate(&self, builder: &Builder) -> bool {
+        builder.bidi_isolation && self.direction == Direction::Rtl
+    }
+}
+
+fn generate_pieces(
+    builder: &Builder,
+    language: &Language,
+    return_static_str: bool,
+    m: &[Piece],
+) -> TokenStream {
+    let builder_type = &builder.builder_type;
+    let isolate = language.isolate(builder);
+    // a text may be split into multiple pieces (escapes)
+    let text = m
+        .iter()
+        .map(|p| match p {
+            Piece::Text(t) => Some(*t),
+            Piece::Param(_, _, _) | Piece::Ref(_) | Piece::TagOpen(_) | Piece::TagClose(_) => None,
+        })
+        .collect::<Option<String>>();
+    if return_static_str {
+        let text = text.expect("invalid return_static_str");
+        return quote!(#text);
+    }
+    if let Some(text) = text {
+        return if text.is_empty() {
+            quote!(<#builder_type as ::typed_i18n::Builder>::empty())
+        } else {
+            quote!(<#builder_type as ::typed_i18n::Builder>::const_str(#text))
+        };
+    }
+    let mut body = quote!(<#builder_type as ::typed_i18n::Builder>::new());
+    let mut text = String::new();
+    let mut pos = 0;
+    while pos < m.len() {
+        let p = &m[pos];
+        pos += 1;
+        if !matches!(p, Piece::Text(_)) {
+            push_text(&mut body, &mut text);
         }
-        let mut body = quote!(<#builder_type as ::typed_i18n::Builder>::new());
-        for p in m {
-            match p {
-                Piece::Text(t) => {
-                    body = quote!(::typed_i18n::Builder::push_const_str(#body, #t));
+        match p {
+            Piece::Text(t) => {
+                text.push_str(t);
+            }
+            Piece::Ref(_) => panic!("unresolved reference"),
+            Piece::TagOpen(t) => {
+                // find the matching end
+                let start = pos;
+                let mut depth = 0;
+                loop {
+                    match m[pos] {
+                        Piece::TagOpen(_) => depth += 1,
+                        Piece::TagClose(_) if depth == 0 => break,
+                        Piece::TagClose(_) => depth -= 1,
+                        _ => {}
+                    }
+                    pos += 1;
                 }
-                Piece::Param(p, ParamType::Str) => {
-                    let p = Ident::new(p, Span::call_site());
-                    body = match str_conversion {
-                        StrConversion::Ref => quote!(::typed_i18n::Builder::push_str(#body, #p)),
-                        StrConversion::AsRef => {
-                            quote!(::typed_i18n::Builder::push_str(#body, #p.as_ref()))
-                        }
-                    };
+                let inner = generate_pieces(builder, language, false, &m[start..pos]);
+                pos += 1;
+                let t = Ident::new(t, Span::call_site());
+                body = match builder.input_conversion {
+                    InputConversion::Value | InputConversion::Into => {
+                        quote!(::typed_i18n::BuilderFromValue::push(#body, #t(#inner)))
+                    }
+                    InputConversion::Ref | InputConversion::AsRef => {
+                        quote!(::typed_i18n::BuilderFromRef::push(#body, &#t(#inner)))
+                    }
+                };
+            }
+            Piece::TagClose(_) => panic!("unbalanced tags"),
+            Piece::Param(_, ParamType::Tag, _) => panic!("tag as a parameter"),
+            Piece::Param(p, ParamType::Str, case) => {
+                let p = Ident::new(p, Span::call_site());
+                if isolate {
+                    body = quote!(::typed_i18n::Builder::push_isolate_start(#body));
                 }
-                Piece::Param(p, ParamType::Typed) => {
-                    let p = Ident::new(p, Span::call_site());
-                    body = match input_conversion {
-                        InputConversion::Value => {
-                            quote!(::typed_i18n::BuilderFromValue::push(#body, #p))
-                        }
-                        InputConversion::Into => {
-                            quote!(::typed_i18n::BuilderFromValue::push(#body, #p.into()))
-                        }
-                        InputConversion::Ref => {
-                            quote!(::typed_i18n::BuilderFromRef::push(#body, #p))
-                        }
-                        InputConversion::AsRef => {
-                            quote!(::typed_i18n::BuilderFromRef::push(#body, #p.as_ref()))
-                        }
-                    };
+                let p = match builder.str_conversion {
+                    StrConversion::Ref => quote!(#p),
+                    StrConversion::AsRef => quote!(#p.as_ref()),
+                };
+                body = if let Some(case) = case {
+                    let case = case.tokens();
+                    let turkic = language.turkic();
+                    quote!(::typed_i18n::Builder::push_str_case(#body, #p, #case, #turkic))
+                } else {
+                    quote!(::typed_i18n::Builder::push_str(#body, #p))
+                };
+                if isolate {
+                    body = quote!(::typed_i18n::Builder::push_isolate_end(#body));
                 }
             }
+            Piece::Param(p, ParamType::Typed, _) => {
+                let p = Ident::new(p, Span::call_site());
+                body = match builder.input_conversion {
+                    InputConversion::Value => {
+                        quote!(::typed_i18n::BuilderFromValue::push(#body, #p))
+                    }
+                    InputConversion::Into => {
+                        quote!(::typed_i18n::BuilderFromValue::push(#body, #p.into()))
+                    }
+                    InputConversion::Ref => {
+                        quote!(::typed_i18n::BuilderFromRef::push(#body, #p))
+                    }
+                    InputConversion::AsRef => {
+                        quote!(::typed_i18n::BuilderFromRef::push(#body, #p.as_ref()))
+                    }
+                };
+            }
         }
-        quote!(::typed_i18n::Builder::finish(#body))
+    }
+    push_text(&mut body, &mut text);
+    quote!(::typed_i18n::Builder::finish(#body))
+}
+
+fn push_text(body: &mut TokenStream, text: &mut String) {
+    if !text.is_empty() {
+        *body = quote!(::typed_i18n::Builder::push_const_str(#body, #text));
+        text.clear();
     }
 }
 
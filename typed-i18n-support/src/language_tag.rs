@@ -0,0 +1,141 @@
+//! Syntax check of BCP 47 language tags (RFC 5646).
+//!
+//! Only well-formedness is checked, the subtags are not looked up in the registry.
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+enum State {
+    Language,
+    ExtLang(usize),
+    Script,
+    Region,
+    Variant,
+    Extension,
+}
+
+/// Check if the tag is a well-formed language tag.
+pub(crate) fn is_valid(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let first = subtags.next().unwrap_or_default();
+    if first.eq_ignore_ascii_case("x") {
+        return is_private_use(subtags);
+    }
+    if !(2..=8).contains(&first.len()) || !first.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return false;
+    }
+
+    let mut state = if first.len() <= 3 {
+        State::ExtLang(0)
+    } else {
+        State::Language
+    };
+    let mut variants = Vec::new();
+    let mut singletons = Vec::new();
+    let mut extension_len = 0;
+
+    while let Some(s) = subtags.next() {
+        let len = s.len();
+        let alpha = s.bytes().all(|b| b.is_ascii_alphabetic());
+        let digit = s.bytes().all(|b| b.is_ascii_digit());
+        let alphanum = s.bytes().all(|b| b.is_ascii_alphanumeric());
+        if !alphanum || len == 0 {
+            return false;
+        }
+        if state == State::Extension && len >= 2 {
+            extension_len += 1;
+            continue;
+        }
+        if len == 1 {
+            if state == State::Extension && extension_len == 0 {
+                return false;
+            }
+            if s.eq_ignore_ascii_case("x") {
+                return is_private_use(subtags);
+            }
+            let singleton = s.to_ascii_lowercase();
+            if singletons.contains(&singleton) {
+                return false;
+            }
+            singletons.push(singleton);
+            state = State::Extension;
+            extension_len = 0;
+        } else if matches!(state, State::ExtLang(n) if n < 3) && len == 3 && alpha {
+            if let State::ExtLang(n) = state {
+                state = State::ExtLang(n + 1);
+            }
+        } else if state < State::Script && len == 4 && alpha {
+            state = State::Script;
+        } else if state < State::Region && ((len == 2 && alpha) || (len == 3 && digit)) {
+            state = State::Region;
+        } else if state <= State::Variant
+            && ((5..=8).contains(&len) || (len == 4 && s.as_bytes()[0].is_ascii_digit()))
+        {
+            let variant = s.to_ascii_lowercase();
+            if variants.contains(&variant) {
+                return false;
+            }
+            variants.push(variant);
+            state = State::Variant;
+        } else {
+            return false;
+        }
+    }
+
+    state != State::Extension || extension_len > 0
+}
+
+fn is_private_use<'a, I: Iterator<Item = &'a str>>(subtags: I) -> bool {
+    let mut count = 0;
+    for s in subtags {
+        if !(1..=8).contains(&s.len()) || !s.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return false;
+        }
+        count += 1;
+    }
+    count > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::language_tag::is_valid;
+
+    #[test]
+    fn valid_tags() {
+        for tag in [
+            "de",
+            "de-AT",
+            "en-au",
+            "zh-Hant-TW",
+            "sr-Latn-RS",
+            "es-419",
+            "zh-yue-HK",
+            "de-CH-1901",
+            "sl-rozaj-biske",
+            "en-US-u-ca-gregory",
+            "en-a-bbb-x-a-ccc",
+            "x-whatever",
+            "tlh",
+        ] {
+            assert!(is_valid(tag), "{tag}");
+        }
+    }
+
+    #[test]
+    fn invalid_tags() {
+        for tag in [
+            "",
+            "d",
+            "de_AT",
+            "de-",
+            "de--AT",
+            "de-AT-AT",
+            "de-CH-1901-1901",
+            "en-a-bbb-a-ccc",
+            "en-u",
+            "en-x",
+            "toolonglanguage",
+            "ä",
+        ] {
+            assert!(!is_valid(tag), "{tag}");
+        }
+    }
+}
@@ -1,12 +1,18 @@
+use crate::attribute::parameters::{parse_bool, parse_charset, parse_complete};
 use crate::attribute::parser::Parser;
+use crate::attribute::{Complete, Fallback, Parameters};
+use crate::charset::Charset;
 use crate::diagnostic::{Diagnostic, Simulated};
+use crate::language_tag;
+use crate::messages::raw::RawMessages;
 use convert_case::{Case, Casing};
 use proc_macro2::Span;
 use std::collections::HashSet;
 use std::mem;
 use std::slice::Iter;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Data, Fields, Ident};
+use syn::{Data, Fields, Ident, Token, Variant};
 
 pub struct Languages(Vec<Language>);
 
@@ -14,6 +20,41 @@ impl Languages {
     pub fn iter(&self) -> Iter<'_, Language> {
         self.into_iter()
     }
+
+    pub(crate) fn default_language(&self) -> &Language {
+        self.iter()
+            .find(|l| l.default)
+            .expect("no default language")
+    }
+
+    /// Add the chars of the charset files (read by `read`) and use the global charset for all languages without an own.
+    pub fn load_charsets<D: Diagnostic, F: FnMut(&str) -> Result<String, String>>(
+        &mut self,
+        diagnostic: &mut D,
+        span: Span,
+        parameters: &Parameters,
+        mut read: F,
+    ) {
+        let mut load = |charset: &mut Option<Charset>, file: Option<&String>| {
+            if let Some(file) = file {
+                match read(file) {
+                    Ok(glyphs) => charset
+                        .get_or_insert_with(Charset::default)
+                        .extend(Charset::from_glyphs(&glyphs)),
+                    Err(err) => diagnostic.emit_error(span, err),
+                }
+            }
+        };
+        let mut global = parameters.charset.clone();
+        load(&mut global, parameters.charset_file.as_ref());
+        for l in &mut self.0 {
+            let file = l.charset_file.take();
+            load(&mut l.charset, file.as_ref());
+            if l.charset.is_none() {
+                l.charset.clone_from(&global);
+            }
+        }
+    }
 }
 
 #[allow(clippy::into_iter_without_iter)] // because of a false positive
@@ -44,8 +85,26 @@ impl<'a> IntoIterator for &'a RawLanguages {
 pub struct Language {
     pub ident: Ident,
     pub name: String,
+    /// The BCP 47 language tag, used for negotiation.
+    pub tag: Option<String>,
     pub fallback: Vec<String>,
     pub default: bool,
+    pub direction: Direction,
+    /// The chars which can be displayed, after [`Languages::load_charsets`] the global one if there is no own.
+    pub charset: Option<Charset>,
+    /// A file with the chars (glyphs), relative to the crate root.
+    pub charset_file: Option<String>,
+    /// Whether the language must have all keys, after [`RawLanguages::into`] the input field `require_complete` if there is no own.
+    pub complete: Option<Complete>,
+    /// The keys of the source language are required for the complete languages (instead of all keys).
+    pub source: bool,
+}
+
+/// The text direction of a language.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    Ltr,
+    Rtl,
 }
 
 impl Language {
@@ -55,8 +114,31 @@ impl Language {
         Language {
             ident: Ident::new(&name.to_case(Case::Pascal), Span::call_site()),
             name: name.to_string(),
+            tag: Self::tag_from_name(name),
             fallback: fallback.iter().map(ToString::to_string).collect(),
             default: false,
+            direction: Direction::Ltr,
+            charset: None,
+            charset_file: None,
+            complete: None,
+            source: false,
+        }
+    }
+
+    /// Whether the turkic case mapping of the dotted and dotless i is used (`tr` and `az`).
+    pub(crate) fn turkic(&self) -> bool {
+        let tag = self.tag.as_deref().unwrap_or(&self.name);
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+        primary.eq_ignore_ascii_case("tr") || primary.eq_ignore_ascii_case("az")
+    }
+
+    /// The tag derived from the name (`en_au` becomes `en-au`), if that is a valid tag.
+    fn tag_from_name(name: &str) -> Option<String> {
+        let tag = name.replace('_', "-");
+        if language_tag::is_valid(&tag) {
+            Some(tag)
+        } else {
+            None
         }
     }
 }
@@ -64,55 +146,7 @@ impl Language {
 impl RawLanguages {
     pub fn parse<D: Diagnostic>(diagnostic: &mut D, span: Span, data: Data) -> Self {
         if let Data::Enum(data) = data {
-            let mut values = Vec::new();
-
-            for v in data.variants {
-                let span = v.span();
-                if !matches!(v.fields, Fields::Unit) {
-                    diagnostic.emit_error(span, "only unit field items are allowed");
-                }
-                let mut has_attr = false;
-                let mut name = None;
-                let mut fallback = Vec::new();
-                let mut default = false;
-
-                for a in v.attrs {
-                    let a_span = a.span();
-                    if let Some(mut parser) = Parser::parse(diagnostic, a) {
-                        if has_attr {
-                            diagnostic.emit_error(a_span, "duplicate variant attribute");
-                        } else {
-                            has_attr = true;
-                            name = parser.remove("name").map(|l| l.1);
-                            if let Some((_, f)) = parser.remove("fallback") {
-                                fallback = f
-                                    .split(|s: char| s.is_whitespace() || s == ',')
-                                    .filter(|s| !s.is_empty())
-                                    .map(ToString::to_string)
-                                    .collect();
-                            }
-                            if let Some((s, v)) = parser.remove("default") {
-                                match v.as_str() {
-                                    "true" => {
-                                        default = true;
-                                    }
-                                    "false" => {}
-                                    _ => diagnostic.emit_error(s, "unknown default value"),
-                                }
-                            }
-                            parser.finish(diagnostic);
-                        }
-                    }
-                }
-
-                let name = name.unwrap_or_else(|| v.ident.to_string().to_case(Case::Snake));
-                values.push(Language {
-                    ident: v.ident,
-                    name,
-                    fallback,
-                    default,
-                });
-            }
+            let values = Self::parse_variants(diagnostic, data.variants);
 
             if values.is_empty() {
                 diagnostic.emit_error(span, "no variants found");
@@ -128,12 +162,134 @@ impl RawLanguages {
         }
     }
 
+    /// Parse the enum of `language_enum!`, all languages of the messages which are not a variant
+    /// are added (in order of their first use) with the name in `PascalCase`.
+    ///
+    /// Errors in the messages are not reported here but by [`Messages::parse`](crate::messages::Messages::parse).
+    pub fn parse_with_messages<D: Diagnostic>(
+        diagnostic: &mut D,
+        span: Span,
+        data: Data,
+        parameters: &Parameters,
+        content: &str,
+    ) -> Self {
+        if let Data::Enum(data) = data {
+            let mut values = Self::parse_variants(diagnostic, data.variants);
+
+            let raw = RawMessages::parse(&mut Simulated::new(), span, parameters, content);
+            for name in raw.language_names() {
+                if values.iter().any(|l| l.name == name) {
+                    continue;
+                }
+                if syn::parse_str::<Ident>(&name.to_case(Case::Pascal)).is_ok() {
+                    values.push(Language::run_new(name, &[]));
+                } else {
+                    diagnostic.emit_error(span, format!("invalid language name {name}"));
+                }
+            }
+
+            RawLanguages(values)
+        } else {
+            diagnostic.emit_error(span, "only enums are supported");
+            diagnostic.should_abort_if_dirty();
+
+            RawLanguages(vec![])
+        }
+    }
+
+    fn parse_variants<D: Diagnostic>(
+        diagnostic: &mut D,
+        variants: Punctuated<Variant, Token![,]>,
+    ) -> Vec<Language> {
+        let mut values = Vec::new();
+
+        for v in variants {
+            let span = v.span();
+            if !matches!(v.fields, Fields::Unit) {
+                diagnostic.emit_error(span, "only unit field items are allowed");
+            }
+            let mut has_attr = false;
+            let mut name = None;
+            let mut tag = None;
+            let mut fallback = Vec::new();
+            let mut default = false;
+            let mut direction = Direction::Ltr;
+            let mut charset = None;
+            let mut charset_file = None;
+            let mut complete = None;
+            let mut source = false;
+
+            for a in v.attrs {
+                let a_span = a.span();
+                if let Some(mut parser) = Parser::parse(diagnostic, a) {
+                    if has_attr {
+                        diagnostic.emit_error(a_span, "duplicate variant attribute");
+                    } else {
+                        has_attr = true;
+                        name = parser.remove("name").map(|l| l.1);
+                        if let Some((s, t)) = parser.remove("tag") {
+                            if language_tag::is_valid(&t) {
+                                tag = Some(t);
+                            } else {
+                                diagnostic.emit_error(s, format!("invalid language tag: {t}"));
+                            }
+                        }
+                        if let Some((_, f)) = parser.remove("fallback") {
+                            fallback = f
+                                .split(|s: char| s.is_whitespace() || s == ',')
+                                .filter(|s| !s.is_empty())
+                                .map(ToString::to_string)
+                                .collect();
+                        }
+                        default = parse_bool(diagnostic, &mut parser, "default");
+                        if let Some((s, d)) = parser.remove("dir") {
+                            match d.as_str() {
+                                "ltr" => {}
+                                "rtl" => {
+                                    direction = Direction::Rtl;
+                                }
+                                _ => diagnostic.emit_error(s, "unknown dir value"),
+                            }
+                        }
+                        charset = parse_charset(diagnostic, &mut parser);
+                        charset_file = parser.remove("charset_file").map(|(_, f)| f);
+                        complete = parse_complete(diagnostic, &mut parser, "complete");
+                        source = parse_bool(diagnostic, &mut parser, "source");
+                        parser.finish(diagnostic);
+                    }
+                }
+            }
+
+            let name = name.unwrap_or_else(|| v.ident.to_string().to_case(Case::Snake));
+            let tag = tag.or_else(|| Language::tag_from_name(&name));
+            values.push(Language {
+                ident: v.ident,
+                name,
+                tag,
+                fallback,
+                default,
+                direction,
+                charset,
+                charset_file,
+                complete,
+                source,
+            });
+        }
+
+        values
+    }
+
     /// Simplified function to run the into conversion, will result in an error if any diagnostics are emitted.
-    pub fn run_into(self) -> Result<Languages, Simulated> {
-        Simulated::run(|diagnostic| self.into(diagnostic, Span::call_site()))
+    pub fn run_into(self, parameters: &Parameters) -> Result<Languages, Simulated> {
+        Simulated::run(|diagnostic| self.into(diagnostic, Span::call_site(), parameters))
     }
 
-    pub fn into<D: Diagnostic>(self, diagnostic: &mut D, span: Span) -> Languages {
+    pub fn into<D: Diagnostic>(
+        self,
+        diagnostic: &mut D,
+        span: Span,
+        parameters: &Parameters,
+    ) -> Languages {
         let mut languages = self.0;
         if languages.is_empty() {
             diagnostic.emit_error(span, "no languages found");
@@ -147,9 +303,13 @@ impl RawLanguages {
         } else if defaults > 1 {
             diagnostic.emit_error(span, "found more than one default language");
         }
+        if languages.iter().filter(|l| l.source).count() > 1 {
+            diagnostic.emit_error(span, "found more than one source language");
+        }
         let names = languages.iter().map(|v| v.name.clone()).collect::<Vec<_>>();
         let mut seen_languages = HashSet::new();
         for v in &mut languages {
+            v.complete.get_or_insert(parameters.require_complete);
             if !seen_languages.insert(&v.name) {
                 diagnostic.emit_error(v.ident.span(), "language defined twice");
             }
@@ -167,11 +327,51 @@ impl RawLanguages {
             v.fallback.clear();
             v.fallback.push(v.name.clone());
             v.fallback.extend(l);
+            let mut missing = Vec::new();
+            let mut reordered = Vec::new();
+            if parameters.fallback == Fallback::Hierarchy {
+                let mut parent = v.name.as_str();
+                while let Some((p, _)) = parent.rsplit_once('_') {
+                    parent = p;
+                    if !names.iter().any(|n| n == parent) {
+                        missing.push(parent);
+                    } else if !v.fallback.iter().any(|f| f == parent) {
+                        // the languages listed before the parent now come after it
+                        let skipped = names
+                            .iter()
+                            .take_while(|n| *n != parent)
+                            .filter(|n| !v.fallback.contains(n))
+                            .map(String::as_str)
+                            .collect::<Vec<_>>();
+                        if !skipped.is_empty() {
+                            reordered
+                                .push(format!("parent {parent} before {}", skipped.join(", ")));
+                        }
+                        v.fallback.push(parent.to_string());
+                    }
+                }
+            }
             for name in &names {
                 if !v.fallback.contains(name) {
                     v.fallback.push(name.clone());
                 }
             }
+            // the inferred chain differs from the listing order or lacks a parent
+            let mut notes = reordered;
+            if !missing.is_empty() {
+                notes.push(format!("unknown parent language: {}", missing.join(", ")));
+            }
+            if !notes.is_empty() {
+                diagnostic.emit_warning(
+                    v.ident.span(),
+                    format!(
+                        "fallback of {} is {}; {}",
+                        v.name,
+                        v.fallback.join(", "),
+                        notes.join("; ")
+                    ),
+                );
+            }
         }
         Languages(languages)
     }
@@ -13,7 +13,11 @@
 //! Support crate for the derive macro for [typed-i18n](https://docs.rs/typed-i18n/latest/typed-i18n/).
 
 pub mod attribute;
+pub mod charset;
 pub mod diagnostic;
 pub(crate) mod generator;
+pub(crate) mod language_tag;
 pub mod languages;
+pub mod localize;
 pub mod messages;
+pub mod named;
@@ -0,0 +1,263 @@
+use crate::attribute::parameters::parse_bool;
+use crate::attribute::parser::Parser;
+use crate::diagnostic::Diagnostic;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Fields, Generics, Ident, Path, Type, Visibility};
+
+/// The name of the attributes of the `Localize` derive macro.
+pub const LOCALIZE_ATTRIBUTE_NAME: &str = "localize";
+
+/// A type (enum or struct) whose values are localized with the named macro of the messages.
+pub struct Localize {
+    vis: Visibility,
+    ident: Ident,
+    generics: Generics,
+    /// The language enum.
+    messages: Type,
+    named_macro: Path,
+    output: Type,
+    prefix: String,
+    display: bool,
+    cases: Vec<Case>,
+}
+
+/// A variant (or the struct) and its message.
+struct Case {
+    /// The path of the variant or the struct.
+    path: TokenStream,
+    key: Ident,
+    /// Whether the fields are named (`Some(true)`), unnamed (`Some(false)`) or if there are none (`None`).
+    named: Option<bool>,
+    /// The fields (in order) as `(binding, parameter)`, `None` if it is skipped.
+    fields: Vec<Option<(Ident, Ident)>>,
+}
+
+impl Localize {
+    #[must_use]
+    pub fn parse<D: Diagnostic>(diagnostic: &mut D, span: Span, input: DeriveInput) -> Self {
+        let mut parser = Self::parser(diagnostic, span, input.attrs);
+        let messages = parse_required(diagnostic, span, &mut parser, "messages");
+        let named_macro = parse_required(diagnostic, span, &mut parser, "named_macro");
+        let output = parse_required(diagnostic, span, &mut parser, "output");
+        let prefix = parser.remove("prefix").map_or(String::new(), |(_, p)| p);
+        let display = parse_bool(diagnostic, &mut parser, "display");
+        let key = parser.remove("key");
+
+        let mut cases = Vec::new();
+        match input.data {
+            Data::Enum(data) => {
+                if let Some((sp, _)) = key {
+                    diagnostic.emit_error(sp, "the key of an enum must be at the variants");
+                }
+                for v in data.variants {
+                    let v_span = v.span();
+                    let mut v_parser = Self::parser(diagnostic, v_span, v.attrs);
+                    let key = v_parser.remove("key");
+                    v_parser.finish(diagnostic);
+                    let variant = v.ident;
+                    cases.push(Case::new(
+                        diagnostic,
+                        v_span,
+                        quote!(Self::#variant),
+                        key,
+                        v.fields,
+                    ));
+                }
+            }
+            Data::Struct(data) => {
+                cases.push(Case::new(diagnostic, span, quote!(Self), key, data.fields));
+            }
+            Data::Union(_) => {
+                diagnostic.emit_error(span, "only enums and structs are supported");
+            }
+        }
+        parser.finish(diagnostic);
+
+        diagnostic.should_abort_if_dirty();
+
+        Localize {
+            vis: input.vis,
+            ident: input.ident,
+            generics: input.generics,
+            messages: messages.unwrap_or_else(|| syn::parse_quote!(())),
+            named_macro: named_macro.unwrap_or_else(|| syn::parse_quote!(t)),
+            output: output.unwrap_or_else(|| syn::parse_quote!(())),
+            prefix,
+            display,
+            cases,
+        }
+    }
+
+    /// The parser of the only `localize` attribute (an empty one if there is none).
+    fn parser<D: Diagnostic>(diagnostic: &mut D, span: Span, attrs: Vec<Attribute>) -> Parser {
+        let mut result = None;
+        for a in attrs {
+            let a_span = a.span();
+            if let Some(parser) = Parser::parse_named(diagnostic, a, LOCALIZE_ATTRIBUTE_NAME) {
+                if result.is_some() {
+                    diagnostic.emit_error(a_span, "duplicate localize attribute");
+                } else {
+                    result = Some(parser);
+                }
+            }
+        }
+        result.unwrap_or_else(|| Parser::empty(span))
+    }
+
+    #[must_use]
+    pub fn generate(&self) -> TokenStream {
+        let Localize {
+            vis,
+            ident,
+            generics,
+            messages,
+            named_macro,
+            output,
+            prefix,
+            display,
+            cases,
+        } = self;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        // hygienic, a field may have the same name
+        let language = Ident::new("language", Span::mixed_site());
+
+        let mut arms = TokenStream::new();
+        for Case {
+            path,
+            key,
+            named,
+            fields,
+        } in cases
+        {
+            let function = Ident::new(&format!("{prefix}{key}"), key.span());
+            let pattern = match named {
+                Some(true) => {
+                    let bindings = fields.iter().flatten().map(|(b, _)| b);
+                    quote!(#path { #(#bindings,)* .. })
+                }
+                Some(false) => {
+                    let bindings = fields
+                        .iter()
+                        .map(|f| f.as_ref().map_or(quote!(_), |(b, _)| quote!(#b)));
+                    quote!(#path ( #(#bindings),* ))
+                }
+                None => quote!(#path),
+            };
+            let args = fields.iter().flatten().map(|(b, p)| quote!(, #p = #b));
+            arms.extend(quote!(
+                #pattern => #named_macro!(#language, #function #(#args)*),
+            ));
+        }
+        let body = if cases.is_empty() {
+            quote!(match *self {})
+        } else {
+            quote!(match self { #arms })
+        };
+
+        let display = if *display {
+            quote!(
+                impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        ::core::fmt::Display::fmt(&self.localize(<#messages>::global()), f)
+                    }
+                }
+            )
+        } else {
+            TokenStream::new()
+        };
+
+        quote!(
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #vis fn localize(&self, #language: #messages) -> #output {
+                    #body
+                }
+            }
+            #display
+        )
+    }
+}
+
+impl Case {
+    fn new<D: Diagnostic>(
+        diagnostic: &mut D,
+        span: Span,
+        path: TokenStream,
+        key: Option<(Span, String)>,
+        fields: Fields,
+    ) -> Self {
+        let key = if let Some((sp, k)) = key {
+            if syn::parse_str::<Ident>(&k).is_ok() {
+                Ident::new(&k, sp)
+            } else {
+                diagnostic.emit_error(sp, format!("invalid key: {k}"));
+                Ident::new("invalid", sp)
+            }
+        } else {
+            diagnostic.emit_error(span, "missing key");
+            Ident::new("invalid", span)
+        };
+        let named = match &fields {
+            Fields::Named(_) => Some(true),
+            Fields::Unnamed(_) => Some(false),
+            Fields::Unit => None,
+        };
+        let mut params = Vec::new();
+        for (pos, f) in fields.into_iter().enumerate() {
+            let f_span = f.span();
+            let mut parser = Localize::parser(diagnostic, f_span, f.attrs);
+            let skip = parse_bool(diagnostic, &mut parser, "skip");
+            let name = parser.remove("name");
+            parser.finish(diagnostic);
+            if skip {
+                params.push(None);
+                continue;
+            }
+            let binding = f
+                .ident
+                .clone()
+                .unwrap_or_else(|| Ident::new(&format!("__field{pos}"), Span::mixed_site()));
+            let param = match (name, &f.ident) {
+                (Some((sp, n)), _) => {
+                    if let Ok(n) = syn::parse_str::<Ident>(&n) {
+                        Ident::new(&n.to_string(), sp)
+                    } else {
+                        diagnostic.emit_error(sp, format!("invalid parameter name: {n}"));
+                        params.push(None);
+                        continue;
+                    }
+                }
+                (None, Some(ident)) => ident.clone(),
+                (None, None) => {
+                    diagnostic.emit_error(f_span, "an unnamed field requires a name or skip");
+                    params.push(None);
+                    continue;
+                }
+            };
+            params.push(Some((binding, param)));
+        }
+        Case {
+            path,
+            key,
+            named,
+            fields: params,
+        }
+    }
+}
+
+fn parse_required<D: Diagnostic, T: syn::parse::Parse>(
+    diagnostic: &mut D,
+    span: Span,
+    parser: &mut Parser,
+    name: &str,
+) -> Option<T> {
+    if let Some((sp, value)) = parser.remove(name) {
+        syn::parse_str(&value)
+            .map_err(|_| diagnostic.emit_error(sp, format!("invalid {name}: {value}")))
+            .ok()
+    } else {
+        diagnostic.emit_error(span, format!("missing {name}"));
+        None
+    }
+}
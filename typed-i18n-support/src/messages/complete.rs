@@ -0,0 +1,70 @@
+use crate::attribute::{Complete, Parameters};
+use crate::diagnostic::Diagnostic;
+use crate::languages::Languages;
+use crate::messages::message::Message;
+use crate::messages::message_line::MessageLine;
+use crate::messages::messages::Messages;
+use crate::messages::piece::Piece;
+
+impl Message<'_> {
+    /// Whether there is an own text, list, variant or constant in the language,
+    /// with `empty_is_missing` an empty text (or list item, or variant) doesn't count.
+    fn is_translated(&self, lang: &str, empty_is_missing: bool) -> bool {
+        let is_empty = |ml: &MessageLine| {
+            empty_is_missing
+                && ml
+                    .borrow_pieces()
+                    .iter()
+                    .all(|p| matches!(p, Piece::Text(t) if t.is_empty()))
+        };
+        if let Some(ml) = self.message_lines.get(lang) {
+            !is_empty(ml)
+        } else if let Some(list) = self.lists.get(lang) {
+            !list.iter().any(is_empty)
+        } else if let Some(variants) = self.variants.get(lang) {
+            !variants.values().any(is_empty)
+        } else {
+            self.constants.contains_key(lang)
+        }
+    }
+}
+
+impl Messages<'_> {
+    /// Check that the complete languages have all keys (of the source language, if there is one).
+    pub(crate) fn check_completeness<D: Diagnostic>(
+        &self,
+        diagnostic: &mut D,
+        parameters: &Parameters,
+        languages: &Languages,
+    ) {
+        let source = languages.iter().find(|l| l.source);
+        for l in languages {
+            let complete = l.complete.unwrap_or(parameters.require_complete);
+            if complete == Complete::No {
+                continue;
+            }
+            let missing = self
+                .inner
+                .iter()
+                .filter(|(_, m)| {
+                    source.map_or(true, |s| {
+                        m.is_translated(&s.name, parameters.empty_is_missing)
+                    })
+                })
+                .filter(|(_, m)| !m.is_translated(&l.name, parameters.empty_is_missing))
+                .map(|(k, _)| k.as_ref())
+                .collect::<Vec<_>>();
+            if missing.is_empty() {
+                continue;
+            }
+            let message = format!("language {} is missing: {}", l.name, missing.join(", "));
+            // the variant of the language
+            let span = l.ident.span();
+            if complete == Complete::Yes {
+                diagnostic.emit_error(span, message);
+            } else {
+                diagnostic.emit_warning(span, message);
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::diagnostic::Diagnostic;
+use crate::messages::messages::Messages;
+use crate::messages::param_type::ParamType;
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use syn::Visibility;
+
+/// A subtree of messages (`_compound: true`) which is generated as a struct.
+pub(crate) struct Compound {
+    /// The path of the subtree, used for the name of the struct.
+    pub(crate) path: Vec<String>,
+    /// The fields (the last segment of the path) and keys of the messages.
+    pub(crate) fields: Vec<(String, String)>,
+    /// The parameters of all messages (in order of the messages).
+    pub(crate) params: Vec<(String, ParamType)>,
+}
+
+impl Compound {
+    /// The name of the struct, the path in `PascalCase`.
+    pub(crate) fn struct_name(&self) -> String {
+        self.path.iter().map(|p| p.to_case(Case::Pascal)).collect()
+    }
+
+    pub(crate) fn struct_ident(&self) -> Ident {
+        Ident::new(&self.struct_name(), Span::call_site())
+    }
+
+    pub(crate) fn generate_struct(&self, vis: &Visibility) -> TokenStream {
+        let struct_ident = self.struct_ident();
+        let fields = self.fields.iter().map(|(field, _)| {
+            let field = Ident::new(field, Span::call_site());
+            quote!(#vis #field: T,)
+        });
+        quote!(
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            #vis struct #struct_ident<T> { #(#fields)* }
+        )
+    }
+}
+
+impl Messages<'_> {
+    /// Collect the compounds and unify the parameters of their messages.
+    pub(crate) fn collect_compounds<D: Diagnostic>(&mut self, diagnostic: &mut D, span: Span) {
+        for (k, m) in &self.inner {
+            let c = if let Some(c) = &m.compound {
+                c
+            } else {
+                continue;
+            };
+            let (field, path) = m.path.split_last().expect("a compound has a path");
+            let compound = self
+                .compounds
+                .entry(Cow::Owned(c.to_string()))
+                .or_insert_with(|| Compound {
+                    path: path.iter().map(ToString::to_string).collect(),
+                    fields: Vec::new(),
+                    params: Vec::new(),
+                });
+            if !m.lists.is_empty() {
+                diagnostic.emit_error(span, format!("compound {c} contains the list {k}"));
+            }
+            if m.constant_type.is_some() {
+                diagnostic.emit_error(span, format!("compound {c} contains the constant {k}"));
+            }
+            if syn::parse_str::<Ident>(field).is_err() {
+                diagnostic.emit_error(
+                    span,
+                    format!(r#"invalid field name "{field}" in compound {c}"#),
+                );
+            }
+            compound.fields.push((field.to_string(), k.to_string()));
+            for (p_name, p_type) in &m.params {
+                if let Some((_, old_p_type)) = compound.params.iter().find(|(x, _)| x == p_name) {
+                    if p_type != old_p_type {
+                        diagnostic.emit_error(
+                            span,
+                            format!("mismatching types for parameter {p_name} in compound {c}"),
+                        );
+                    }
+                } else {
+                    compound.params.push((p_name.clone(), *p_type));
+                }
+            }
+        }
+        // the structs are generated next to each other
+        let mut struct_names = HashMap::<String, &str>::new();
+        for (c, compound) in &self.compounds {
+            let name = compound.struct_name();
+            if syn::parse_str::<Ident>(&name).is_err() {
+                diagnostic.emit_error(
+                    span,
+                    format!(r#"invalid struct name "{name}" of compound {c}"#),
+                );
+            } else if let Some(other) = struct_names.insert(name.clone(), c) {
+                diagnostic.emit_error(
+                    span,
+                    format!("compounds {other} and {c} have the same struct name {name}"),
+                );
+            }
+        }
+    }
+}
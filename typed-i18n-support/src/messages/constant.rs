@@ -0,0 +1,198 @@
+use crate::diagnostic::Diagnostic;
+use crate::languages::Languages;
+use crate::messages::message::Message;
+use crate::messages::raw::RawMessages;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde::Serializer;
+use std::borrow::Cow;
+use std::fmt;
+use syn::{LitBool, LitInt, PathArguments, Type, TypePath};
+
+/// A typed value of a message with a `_type`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum Constant {
+    Int(i128),
+    Bool(bool),
+    /// A variant of an enum.
+    Variant(String),
+}
+
+/// The kind of a `_type`.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ConstantType {
+    /// An integer with the range of the type.
+    Int(i128, i128),
+    Bool,
+    Enum,
+}
+
+impl ConstantType {
+    /// The kind of the type, `None` if it's neither an integer, `bool` nor a path of an enum.
+    pub(crate) fn new(ty: &Type) -> Option<Self> {
+        let path = if let Type::Path(TypePath { qself: None, path }) = ty {
+            path
+        } else {
+            return None;
+        };
+        let last = path.segments.last()?;
+        if path
+            .segments
+            .iter()
+            .any(|s| !matches!(s.arguments, PathArguments::None))
+            || ["char", "f32", "f64", "str", "String"].contains(&last.ident.to_string().as_str())
+        {
+            return None;
+        }
+        let name = quote!(#ty).to_string();
+        Some(match name.as_str() {
+            "bool" => ConstantType::Bool,
+            "i8" => ConstantType::Int(i8::MIN.into(), i8::MAX.into()),
+            "i16" => ConstantType::Int(i16::MIN.into(), i16::MAX.into()),
+            "i32" => ConstantType::Int(i32::MIN.into(), i32::MAX.into()),
+            "i64" | "isize" => ConstantType::Int(i64::MIN.into(), i64::MAX.into()),
+            "i128" => ConstantType::Int(i128::MIN, i128::MAX),
+            "u8" => ConstantType::Int(0, u8::MAX.into()),
+            "u16" => ConstantType::Int(0, u16::MAX.into()),
+            "u32" => ConstantType::Int(0, u32::MAX.into()),
+            "u64" | "usize" => ConstantType::Int(0, u64::MAX.into()),
+            // the values are parsed as i128
+            "u128" => ConstantType::Int(0, i128::MAX),
+            _ => ConstantType::Enum,
+        })
+    }
+
+    /// Parse a value, `None` if it's not valid for the type.
+    pub(crate) fn parse(self, value: &str) -> Option<Constant> {
+        match self {
+            ConstantType::Int(min, max) => value
+                .parse()
+                .ok()
+                .filter(|v| (min..=max).contains(v))
+                .map(Constant::Int),
+            ConstantType::Bool => match value {
+                "true" => Some(Constant::Bool(true)),
+                "false" => Some(Constant::Bool(false)),
+                _ => None,
+            },
+            ConstantType::Enum => syn::parse_str::<Ident>(value)
+                .ok()
+                .map(|_| Constant::Variant(value.to_string())),
+        }
+    }
+}
+
+impl Constant {
+    /// The expression of the value.
+    pub(crate) fn generate(&self, ty: &Type) -> TokenStream {
+        match self {
+            Constant::Int(i) => {
+                // a literal can't be negative
+                let abs = LitInt::new(&i.unsigned_abs().to_string(), Span::call_site());
+                if *i < 0 {
+                    quote!(-#abs)
+                } else {
+                    quote!(#abs)
+                }
+            }
+            Constant::Bool(b) => {
+                let b = LitBool::new(*b, Span::call_site());
+                quote!(#b)
+            }
+            Constant::Variant(v) => {
+                let v = Ident::new(v, Span::call_site());
+                quote!(#ty::#v)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constant::Int(i) => write!(f, "{i}"),
+            Constant::Bool(b) => write!(f, "{b}"),
+            Constant::Variant(v) => f.write_str(v),
+        }
+    }
+}
+
+impl ::serde::Serialize for Constant {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Constant::Int(i) => serializer.serialize_i128(*i),
+            Constant::Bool(b) => serializer.serialize_bool(*b),
+            Constant::Variant(v) => serializer.serialize_str(v),
+        }
+    }
+}
+
+impl RawMessages<'_> {
+    /// Parse the values of a message with a `_type`.
+    pub(crate) fn parse_constant<'b, D: Diagnostic>(
+        diagnostic: &mut D,
+        span: Span,
+        languages: &Languages,
+        k: Cow<'b, str>,
+        mut message: Message<'b>,
+        values: impl Iterator<Item = (Cow<'b, str>, Cow<'b, str>)>,
+        has_texts: bool,
+    ) -> Option<(Cow<'b, str>, Message<'b>)> {
+        let constant_type = message.constant_type.as_deref().unwrap_or_default();
+        let constant_type = if let Ok(ty) = syn::parse_str::<Type>(constant_type) {
+            if let Some(constant_type) = ConstantType::new(&ty) {
+                constant_type
+            } else {
+                diagnostic.emit_error(
+                    span,
+                    format!("unsupported type {constant_type} in key {k}, expected an integer, bool or enum"),
+                );
+                return None;
+            }
+        } else {
+            diagnostic.emit_error(span, format!("invalid type {constant_type} in key {k}"));
+            return None;
+        };
+        // the key is the name of the function
+        if k.starts_with('-') {
+            diagnostic.emit_error(span, format!("term {k} can't have a _type"));
+        } else if syn::parse_str::<Ident>(&k).is_err() {
+            diagnostic.emit_error(span, format!(r#"invalid constant name "{k}""#));
+        }
+        if has_texts {
+            diagnostic.emit_error(span, format!("constant {k} can't have lists or variants"));
+        }
+        if message.declared_params.is_some() || message.transform.is_some() {
+            diagnostic.emit_error(
+                span,
+                format!("constant {k} can't have _params or _transform"),
+            );
+        }
+        let mut has_values = false;
+        for (lang, value) in values {
+            has_values = true;
+            if !languages.iter().any(|l| l.name == lang) {
+                diagnostic.emit_error(span, format!("language {lang} key {k} is not known"));
+            } else if let Some(constant) = constant_type.parse(&value) {
+                message.constants.insert(lang, constant);
+            } else {
+                diagnostic.emit_error(
+                    span,
+                    format!(
+                        "invalid value {value} of type {} in {k}.{lang}",
+                        message.constant_type.as_deref().unwrap_or_default()
+                    ),
+                );
+            }
+        }
+        if has_values {
+            Some((k, message))
+        } else {
+            diagnostic.emit_error(span, format!("key {k} has no values"));
+            None
+        }
+    }
+}
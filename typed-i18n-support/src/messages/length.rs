@@ -0,0 +1,131 @@
+use crate::attribute::Parameters;
+use crate::diagnostic::Diagnostic;
+use crate::languages::Languages;
+use crate::messages::messages::Messages;
+use crate::messages::metadata::LengthUnit;
+use crate::messages::piece::Piece;
+use proc_macro2::Span;
+
+impl LengthUnit {
+    pub(crate) fn measure(self, text: &str) -> usize {
+        match self {
+            LengthUnit::Chars => text.chars().count(),
+            LengthUnit::Bytes => text.len(),
+            LengthUnit::Columns => text.chars().map(columns).sum(),
+        }
+    }
+}
+
+/// The display width of a char (an approximation of the east asian width).
+fn columns(c: char) -> usize {
+    let c = u32::from(c);
+    let zero = [
+        (0x0300, 0x036F),
+        (0x0483, 0x0489),
+        (0x0591, 0x05BD),
+        (0x0610, 0x061A),
+        (0x064B, 0x065F),
+        (0x1AB0, 0x1AFF),
+        (0x1DC0, 0x1DFF),
+        (0x200B, 0x200F),
+        (0x20D0, 0x20FF),
+        (0xFE00, 0xFE0F),
+        (0xFE20, 0xFE2F),
+        (0xE0100, 0xE01EF),
+    ];
+    let wide = [
+        (0x1100, 0x115F),
+        (0x231A, 0x231B),
+        (0x2329, 0x232A),
+        (0x2E80, 0x303E),
+        (0x3041, 0x33FF),
+        (0x3400, 0x4DBF),
+        (0x4E00, 0x9FFF),
+        (0xA000, 0xA4CF),
+        (0xA960, 0xA97F),
+        (0xAC00, 0xD7A3),
+        (0xF900, 0xFAFF),
+        (0xFE10, 0xFE19),
+        (0xFE30, 0xFE6F),
+        (0xFF00, 0xFF60),
+        (0xFFE0, 0xFFE6),
+        (0x1F300, 0x1F64F),
+        (0x1F900, 0x1F9FF),
+        (0x20000, 0x2FFFD),
+        (0x30000, 0x3FFFD),
+    ];
+    if zero.iter().any(|(a, b)| (*a..=*b).contains(&c)) {
+        0
+    } else if wide.iter().any(|(a, b)| (*a..=*b).contains(&c)) {
+        2
+    } else {
+        1
+    }
+}
+
+/// The length of the (flattened) pieces, each parameter has the length `param_length`.
+fn measure(unit: LengthUnit, param_length: usize, pieces: &[Piece<'_>]) -> usize {
+    pieces
+        .iter()
+        .map(|p| match p {
+            Piece::Text(t) => unit.measure(t),
+            Piece::Param(..) => param_length,
+            Piece::Ref(_) | Piece::TagOpen(_) | Piece::TagClose(_) => 0,
+        })
+        .sum()
+}
+
+impl Messages<'_> {
+    /// Check the texts which each language displays (with the fallback and references) against
+    /// the `_max_length` of the messages.
+    pub(crate) fn check_max_lengths<D: Diagnostic>(
+        &self,
+        diagnostic: &mut D,
+        span: Span,
+        parameters: &Parameters,
+        languages: &Languages,
+    ) {
+        for (k, m) in &self.inner {
+            let max_length = if let Some(max_length) = m.metadata.max_length {
+                max_length
+            } else {
+                continue;
+            };
+            for l in languages {
+                for (label, pieces) in self.displayed_lines(l, m) {
+                    let length = if let Some(transform) = m.transform {
+                        let texts = transform.message_texts(&pieces, l.turkic());
+                        let pieces = transform.apply_message(&pieces, &texts);
+                        measure(max_length.unit, parameters.param_length, &pieces)
+                    } else {
+                        measure(max_length.unit, parameters.param_length, &pieces)
+                    };
+                    if length > max_length.length {
+                        diagnostic.emit_error(
+                            span,
+                            format!(
+                                "{k}.{label} is {length} {} long, the maximum is {}",
+                                max_length.unit, max_length.length
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::messages::metadata::LengthUnit;
+
+    #[test]
+    fn lengths() {
+        assert_eq!(LengthUnit::Chars.measure("Grüße"), 5);
+        assert_eq!(LengthUnit::Bytes.measure("Grüße"), 7);
+        assert_eq!(LengthUnit::Columns.measure("Grüße"), 5);
+        assert_eq!(LengthUnit::Columns.measure("保存"), 4);
+        assert_eq!(LengthUnit::Columns.measure("e\u{301}"), 1);
+        assert_eq!(LengthUnit::Columns.measure("ＡＢ"), 4);
+    }
+}
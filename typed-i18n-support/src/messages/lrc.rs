@@ -1,6 +1,6 @@
 use crate::diagnostic::Diagnostic;
 use crate::messages::messages::Messages;
-use crate::messages::raw::RawMessages;
+use crate::messages::raw::{RawMessage, RawMessages, RawMeta};
 use indexmap::IndexMap;
 use proc_macro2::Span;
 use std::borrow::Cow;
@@ -13,7 +13,7 @@ impl<'a> RawMessages<'a> {
         content: &'a str,
     ) -> Self {
         let mut current_msg = None;
-        let mut current = IndexMap::new();
+        let mut current = RawMessage::new(vec![]);
         let mut result = IndexMap::new();
         let mut key_pos = 0;
         for (pos, line) in content.lines().enumerate() {
@@ -23,7 +23,10 @@ impl<'a> RawMessages<'a> {
             } else if let Some(line) = line.strip_prefix('#') {
                 if let Some(cm) = current_msg.take() {
                     if result
-                        .insert(Cow::Borrowed(cm), (vec![], mem::take(&mut current)))
+                        .insert(
+                            Cow::Borrowed(cm),
+                            mem::replace(&mut current, RawMessage::new(vec![])),
+                        )
                         .is_some()
                     {
                         diagnostic
@@ -38,10 +41,29 @@ impl<'a> RawMessages<'a> {
             } else {
                 let (a, b) = line.split_once(' ').unwrap_or((line, ""));
                 let a = a.trim();
-                if current
-                    .insert(Cow::Borrowed(a), Cow::Borrowed(b.trim()))
-                    .is_some()
-                {
+                if a.starts_with('_') {
+                    let cm = current_msg.unwrap_or_default();
+                    let b = b.trim();
+                    // like in yaml and json `_deprecated` may be a bool instead of a note
+                    let value = match (a, b) {
+                        ("_deprecated", "true") => RawMeta::Bool(true),
+                        ("_deprecated", "false") => RawMeta::Bool(false),
+                        _ => RawMeta::String(Cow::Borrowed(b)),
+                    };
+                    current.set_meta(diagnostic, span, cm, a, value);
+                } else if let Some(lang) = a.strip_suffix("[]") {
+                    current
+                        .lists
+                        .entry(Cow::Borrowed(lang))
+                        .or_default()
+                        .push(Cow::Borrowed(b.trim()));
+                } else if !current.insert(
+                    diagnostic,
+                    span,
+                    current_msg.unwrap_or_default(),
+                    Cow::Borrowed(a),
+                    Cow::Borrowed(b.trim()),
+                ) {
                     diagnostic.emit_error(span, format!("duplicate language {a} at line {pos}"));
                 }
             }
@@ -49,7 +71,10 @@ impl<'a> RawMessages<'a> {
 
         if let Some(cm) = current_msg {
             if result
-                .insert(Cow::Borrowed(cm), (vec![], mem::take(&mut current)))
+                .insert(
+                    Cow::Borrowed(cm),
+                    mem::replace(&mut current, RawMessage::new(vec![])),
+                )
                 .is_some()
             {
                 diagnostic.emit_error(span, format!("duplicate key {cm} at line {key_pos}"));
@@ -68,12 +93,94 @@ impl Messages<'_> {
             output.push('#');
             output.push_str(k);
             output.push('\n');
+            if let Some(declared_params) = &m.declared_params {
+                output.push_str("_params");
+                for p in declared_params {
+                    output.push(' ');
+                    output.push_str(p);
+                }
+                output.push('\n');
+            }
+            if let Some(transform) = m.transform {
+                output.push_str("_transform ");
+                output.push_str(transform.name());
+                output.push('\n');
+            }
+            if let Some(constant_type) = &m.constant_type {
+                output.push_str("_type ");
+                output.push_str(constant_type);
+                output.push('\n');
+            }
+            let metadata = &m.metadata;
+            if let Some(description) = &metadata.description {
+                output.push_str("_description ");
+                output.push_str(description);
+                output.push('\n');
+            }
+            if let Some(note) = &metadata.note {
+                output.push_str("_note ");
+                output.push_str(note);
+                output.push('\n');
+            }
+            if !metadata.tags.is_empty() {
+                output.push_str("_tags");
+                for t in &metadata.tags {
+                    output.push(' ');
+                    output.push_str(t);
+                }
+                output.push('\n');
+            }
+            if let Some(max_length) = metadata.max_length {
+                output.push_str("_max_length ");
+                output.push_str(&max_length.to_string());
+                output.push('\n');
+            }
+            if let Some(deprecated) = &m.deprecated {
+                output.push_str("_deprecated");
+                if !deprecated.is_empty() {
+                    output.push(' ');
+                    output.push_str(deprecated);
+                }
+                output.push('\n');
+            }
+            if !m.aliases.is_empty() {
+                output.push_str("_aliases");
+                for a in &m.aliases {
+                    output.push(' ');
+                    output.push_str(a);
+                }
+                output.push('\n');
+            }
+            for (l, constant) in &m.constants {
+                output.push_str(l);
+                output.push(' ');
+                output.push_str(&constant.to_string());
+                output.push('\n');
+            }
             for (l, ml) in &m.message_lines {
                 output.push_str(l);
                 output.push(' ');
                 output.push_str(ml.borrow_line());
                 output.push('\n');
             }
+            for (l, list) in &m.lists {
+                for ml in list {
+                    output.push_str(l);
+                    output.push_str("[] ");
+                    output.push_str(ml.borrow_line());
+                    output.push('\n');
+                }
+            }
+            for (l, vm) in &m.variants {
+                for (v, ml) in vm {
+                    output.push_str(l);
+                    output.push(':');
+                    output.push_str(v);
+                    output.push(' ');
+                    output.push_str(ml.borrow_line());
+                    output.push('\n');
+                }
+            }
             output.push('\n');
         }
         output
@@ -1,5 +1,8 @@
+use crate::messages::constant::Constant;
 use crate::messages::message_line::MessageLine;
+use crate::messages::metadata::Metadata;
 use crate::messages::param_type::ParamType;
+use crate::messages::transform::Transform;
 use indexmap::IndexMap;
 use std::borrow::Cow;
 
@@ -9,9 +12,41 @@ pub struct Message<'a> {
     pub(crate) path: Vec<Cow<'a, str>>,
     #[serde(skip)]
     pub(crate) params: Vec<(String, ParamType)>,
+    #[serde(rename = "_params", skip_serializing_if = "Option::is_none")]
+    pub(crate) declared_params: Option<Vec<Cow<'a, str>>>,
+    #[serde(rename = "_transform", skip_serializing_if = "Option::is_none")]
+    pub(crate) transform: Option<Transform>,
+    /// The type of a constant (`_type`).
+    #[serde(rename = "_type", skip_serializing_if = "Option::is_none")]
+    pub(crate) constant_type: Option<Cow<'a, str>>,
+    /// The information for the translators.
+    #[serde(flatten)]
+    pub(crate) metadata: Metadata<'a>,
+    /// The note of a deprecated message, empty if there is none.
+    #[serde(
+        rename = "_deprecated",
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_deprecated"
+    )]
+    pub(crate) deprecated: Option<Cow<'a, str>>,
+    /// The old keys of the message.
+    #[serde(rename = "_aliases", skip_serializing_if = "Vec::is_empty")]
+    pub(crate) aliases: Vec<Cow<'a, str>>,
     #[serde(flatten)]
     #[allow(clippy::struct_field_names)]
     pub(crate) message_lines: IndexMap<Cow<'a, str>, MessageLine<'a>>,
+    /// The variants of a term per language.
+    #[serde(flatten)]
+    pub(crate) variants: IndexMap<Cow<'a, str>, IndexMap<Cow<'a, str>, MessageLine<'a>>>,
+    /// The items of a list per language.
+    #[serde(flatten)]
+    pub(crate) lists: IndexMap<Cow<'a, str>, Vec<MessageLine<'a>>>,
+    /// The values of a constant per language.
+    #[serde(flatten)]
+    pub(crate) constants: IndexMap<Cow<'a, str>, Constant>,
+    /// The key of the compound message this message is part of.
+    #[serde(skip)]
+    pub(crate) compound: Option<Cow<'a, str>>,
 }
 
 impl<'a, 'b: 'a> IntoIterator for &'b Message<'a> {
@@ -34,6 +69,33 @@ impl<'a> Message<'a> {
     pub fn path(&self) -> &[impl AsRef<str> + 'a] {
         &self.path
     }
+
+    #[must_use]
+    pub fn metadata(&self) -> &Metadata<'a> {
+        &self.metadata
+    }
+
+    /// Whether there is a text or a list in the language.
+    pub(crate) fn has_language(&self, lang: &str) -> bool {
+        self.message_lines.contains_key(lang) || self.lists.contains_key(lang)
+    }
+
+    /// The length of the lists (all have the same), `None` if the message is not a list.
+    pub(crate) fn list_len(&self) -> Option<usize> {
+        self.lists.values().next().map(Vec::len)
+    }
+}
+
+/// A deprecated message without a note is written as `true`.
+#[allow(clippy::ref_option)] // the signature is required by serde
+fn serialize_deprecated<S: ::serde::Serializer>(
+    deprecated: &Option<Cow<'_, str>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match deprecated.as_deref() {
+        Some("") | None => serializer.serialize_bool(true),
+        Some(note) => serializer.serialize_str(note),
+    }
 }
 
 pub struct MessageIter<'a, 'b> {
@@ -1,20 +1,23 @@
 use crate::attribute::Parameters;
 use crate::diagnostic::{Diagnostic, Simulated};
 use crate::languages::Languages;
+use crate::messages::compound::Compound;
 use crate::messages::message::Message;
 use crate::messages::raw::RawMessages;
-use crate::messages::serde::{MessagesTreeInner, SerdeInput};
+use crate::messages::serde::MessagesTreeInner;
 use indexmap::IndexMap;
-use proc_macro2::Span;
+use proc_macro2::{Ident, Span};
 use std::borrow::Cow;
-use std::ffi::OsStr;
-use std::path::Path;
 
 #[derive(::serde::Serialize)]
 pub struct Messages<'a> {
     _version: usize,
     #[serde(flatten)]
-    inner: IndexMap<Cow<'a, str>, Message<'a>>,
+    pub(crate) inner: IndexMap<Cow<'a, str>, Message<'a>>,
+    #[serde(skip)]
+    pub(crate) separator: String,
+    #[serde(skip)]
+    pub(crate) compounds: IndexMap<Cow<'a, str>, Compound>,
 }
 
 #[allow(clippy::into_iter_without_iter)] // because of a false positive
@@ -30,8 +33,13 @@ impl<'a, 'b: 'a> IntoIterator for &'b Messages<'a> {
 
 impl<'a> Messages<'a> {
     #[must_use]
-    pub(crate) fn new(inner: IndexMap<Cow<'a, str>, Message<'a>>) -> Self {
-        Self { _version: 2, inner }
+    pub(crate) fn new(inner: IndexMap<Cow<'a, str>, Message<'a>>, separator: &str) -> Self {
+        Self {
+            _version: 2,
+            inner,
+            separator: separator.to_string(),
+            compounds: IndexMap::new(),
+        }
     }
 
     /// Simplified function to run the parser, will result in an error if any diagnostics are emitted.
@@ -59,49 +67,37 @@ impl<'a> Messages<'a> {
         languages: &Languages,
         content: &'a str,
     ) -> Self {
-        let raw = match Path::new(&parameters.filename)
-            .extension()
-            .and_then(OsStr::to_str)
-        {
-            Some("lrc") => RawMessages::parse_lrc(diagnostic, span, content),
-            Some("yaml") => match serde_yaml::from_str::<SerdeInput>(content) {
-                Ok(key_map) => {
-                    RawMessages::parse_serde(diagnostic, span, &parameters.separator, key_map)
-                }
-                Err(err) => {
-                    diagnostic.emit_error(span, format!("Invalid YAML format, {err}"));
-                    RawMessages(IndexMap::default())
-                }
-            },
-            Some("json") => match serde_json::from_str::<SerdeInput>(content) {
-                Ok(key_map) => {
-                    RawMessages::parse_serde(diagnostic, span, &parameters.separator, key_map)
-                }
-                Err(err) => {
-                    diagnostic.emit_error(span, format!("Invalid JSON format, {err}"));
-                    RawMessages(IndexMap::default())
-                }
-            },
-            Some(ext) => {
-                diagnostic.emit_error(span, format!("Unsupported file extension {ext:?}"));
-                RawMessages(IndexMap::default())
-            }
-            None => {
-                diagnostic.emit_error(
-                    span,
-                    format!("No file extension on {:?}", &parameters.filename),
-                );
-                RawMessages(IndexMap::default())
-            }
-        };
+        let raw = RawMessages::parse(diagnostic, span, parameters, content);
         diagnostic.should_abort_if_dirty();
-        RawMessages::parse_values(diagnostic, span, raw, languages)
+        RawMessages::parse_values(diagnostic, span, parameters, raw, languages)
     }
 
     #[must_use]
     pub fn iter<'b: 'a>(&'b self) -> MessagesIter<'a, 'b> {
         self.into_iter()
     }
+
+    /// The aliases must be valid names which are neither keys nor aliases of other messages.
+    pub(crate) fn check_aliases<D: Diagnostic>(&self, diagnostic: &mut D, span: Span) {
+        let mut seen = Vec::new();
+        for (k, m) in &self.inner {
+            if !m.aliases.is_empty() && k.starts_with('-') {
+                diagnostic.emit_error(span, format!("key {k} can't have aliases"));
+                continue;
+            }
+            for a in &m.aliases {
+                if syn::parse_str::<Ident>(a).is_err() {
+                    diagnostic.emit_error(span, format!(r#"invalid alias "{a}" in key {k}"#));
+                } else if self.inner.contains_key(a) || self.compounds.contains_key(a) {
+                    diagnostic.emit_error(span, format!("alias {a} of key {k} is also a key"));
+                } else if seen.contains(&a) {
+                    diagnostic.emit_error(span, format!("duplicate alias {a} in key {k}"));
+                } else {
+                    seen.push(a);
+                }
+            }
+        }
+    }
 }
 
 pub struct MessagesIter<'a, 'b> {
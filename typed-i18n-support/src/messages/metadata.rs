@@ -0,0 +1,117 @@
+use serde::Serializer;
+use std::borrow::Cow;
+use std::fmt;
+
+/// Information for the translators, shown in the documentation of the generated functions.
+#[derive(Default, ::serde::Serialize)]
+pub struct Metadata<'a> {
+    /// What the message is about (`_description`).
+    #[serde(rename = "_description", skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<Cow<'a, str>>,
+    /// A note for the translators (`_note`).
+    #[serde(rename = "_note", skip_serializing_if = "Option::is_none")]
+    pub(crate) note: Option<Cow<'a, str>>,
+    /// Tags to group the messages (`_tags`).
+    #[serde(rename = "_tags", skip_serializing_if = "Vec::is_empty")]
+    pub(crate) tags: Vec<Cow<'a, str>>,
+    /// The maximum length of the texts (`_max_length`).
+    #[serde(rename = "_max_length", skip_serializing_if = "Option::is_none")]
+    pub(crate) max_length: Option<MaxLength>,
+}
+
+/// The maximum length of the texts of a message, checked by the derive macro.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MaxLength {
+    pub length: usize,
+    pub unit: LengthUnit,
+}
+
+/// How the length of a text is measured.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LengthUnit {
+    /// Unicode scalar values.
+    Chars,
+    /// Bytes of the UTF-8 encoding.
+    Bytes,
+    /// Display columns, east asian wide and fullwidth chars take two columns and combining marks none.
+    Columns,
+}
+
+impl<'a> Metadata<'a> {
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    #[must_use]
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    #[must_use]
+    pub fn tags(&self) -> &[impl AsRef<str> + 'a] {
+        &self.tags
+    }
+
+    #[must_use]
+    pub fn max_length(&self) -> Option<MaxLength> {
+        self.max_length
+    }
+}
+
+impl MaxLength {
+    /// Parse `20`, `20 chars`, `20 bytes` or `20 columns`.
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        let (length, unit) = s.trim().split_once(' ').unwrap_or((s.trim(), "chars"));
+        let unit = match unit.trim() {
+            "chars" => LengthUnit::Chars,
+            "bytes" => LengthUnit::Bytes,
+            "columns" => LengthUnit::Columns,
+            _ => return None,
+        };
+        Some(MaxLength {
+            length: length.parse().ok()?,
+            unit,
+        })
+    }
+}
+
+/// The unit `chars` is omitted.
+impl fmt::Display for MaxLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.unit == LengthUnit::Chars {
+            write!(f, "{}", self.length)
+        } else {
+            write!(f, "{} {}", self.length, self.unit)
+        }
+    }
+}
+
+impl ::serde::Serialize for MaxLength {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.unit == LengthUnit::Chars {
+            serializer.serialize_u64(self.length as u64)
+        } else {
+            serializer.collect_str(self)
+        }
+    }
+}
+
+impl LengthUnit {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            LengthUnit::Chars => "chars",
+            LengthUnit::Bytes => "bytes",
+            LengthUnit::Columns => "columns",
+        }
+    }
+}
+
+impl fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
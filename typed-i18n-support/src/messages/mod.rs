@@ -1,11 +1,20 @@
+pub(crate) mod complete;
+pub(crate) mod compound;
+pub(crate) mod constant;
+pub(crate) mod length;
 pub(crate) mod lrc;
 pub(crate) mod message;
 pub(crate) mod message_line;
 pub(crate) mod messages;
+pub(crate) mod metadata;
 pub(crate) mod param_type;
 pub(crate) mod piece;
+pub(crate) mod placeholders;
 pub(crate) mod raw;
+pub(crate) mod reference;
 pub(crate) mod serde;
+pub(crate) mod transform;
 
 pub use message::{Message, MessageIter};
 pub use messages::{Messages, MessagesAsTree, MessagesIter};
+pub use metadata::{LengthUnit, MaxLength, Metadata};
@@ -2,4 +2,6 @@
 pub(crate) enum ParamType {
     Str,
     Typed,
+    /// A markup tag, wraps the inner content.
+    Tag,
 }
@@ -1,7 +1,15 @@
 use crate::messages::param_type::ParamType;
+use crate::messages::transform::Transform;
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone)]
 pub(crate) enum Piece<'a> {
     Text(&'a str),
-    Param(&'a str, ParamType),
+    /// A parameter with an optional case transform.
+    Param(&'a str, ParamType, Option<Transform>),
+    /// A reference to another message.
+    Ref(&'a str),
+    /// The start of a markup tag.
+    TagOpen(&'a str),
+    /// The end of a markup tag.
+    TagClose(&'a str),
 }
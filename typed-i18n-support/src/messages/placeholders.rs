@@ -0,0 +1,246 @@
+use crate::attribute::Placeholders;
+use crate::messages::param_type::ParamType;
+use crate::messages::piece::Piece;
+use crate::messages::transform::Transform;
+
+impl Placeholders {
+    /// Split the message into pieces, returns `false` on a parse error.
+    ///
+    /// The parameter names are not checked.
+    pub(crate) fn scan<'b>(self, msg: &'b str, r: &mut Vec<Piece<'b>>) -> bool {
+        match self {
+            Placeholders::Percent => scan_percent(msg, r),
+            Placeholders::Icu => scan_icu(msg, r),
+            Placeholders::I18next => scan_i18next(msg, r),
+        }
+    }
+}
+
+/// Split the texts at the markup tags `<name>` and `</name>`, escape: `<<`.
+pub(crate) fn scan_markup(pieces: Vec<Piece<'_>>) -> Vec<Piece<'_>> {
+    let mut r = Vec::new();
+    for p in pieces {
+        if let Piece::Text(t) = p {
+            scan_tags(t, &mut r);
+        } else {
+            r.push(p);
+        }
+    }
+    r
+}
+
+fn scan_tags<'b>(msg: &'b str, r: &mut Vec<Piece<'b>>) {
+    let bytes = msg.as_bytes();
+    let mut pos = 0;
+    let mut start = 0;
+
+    while pos < bytes.len() {
+        if bytes[pos] != b'<' {
+            pos += 1;
+        } else if bytes.get(pos + 1) == Some(&b'<') {
+            // escaped `<`: keep the first char and skip the second
+            push_text(r, &msg[start..=pos]);
+            pos += 2;
+            start = pos;
+        } else {
+            let close = bytes.get(pos + 1) == Some(&b'/');
+            let name_start = pos + 1 + usize::from(close);
+            let name_end = msg[name_start..]
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .map_or(msg.len(), |e| name_start + e);
+            if name_end > name_start && bytes.get(name_end) == Some(&b'>') {
+                push_text(r, &msg[start..pos]);
+                let name = &msg[name_start..name_end];
+                r.push(if close {
+                    Piece::TagClose(name)
+                } else {
+                    Piece::TagOpen(name)
+                });
+                pos = name_end + 1;
+                start = pos;
+            } else {
+                // not a tag
+                pos += 1;
+            }
+        }
+    }
+    push_text(r, &msg[start..]);
+}
+
+fn push_text<'b>(r: &mut Vec<Piece<'b>>, text: &'b str) {
+    if !text.is_empty() {
+        r.push(Piece::Text(text));
+    }
+}
+
+/// A parameter `name`, `name, typed` or `name, upper` (the separator of the format is a comma in icu and i18next)
+/// or a reference `@key`.
+fn param(inner: &str) -> Piece<'_> {
+    if let Some(r) = inner.trim().strip_prefix('@') {
+        return Piece::Ref(r.trim());
+    }
+    if let Some((name, format)) = inner.split_once(',') {
+        let format = format.trim();
+        if format == "typed" {
+            return Piece::Param(name.trim(), ParamType::Typed, None);
+        } else if let Some(transform) = Transform::from_name(format) {
+            return Piece::Param(name.trim(), ParamType::Str, Some(transform));
+        }
+    }
+    Piece::Param(inner.trim(), ParamType::Str, None)
+}
+
+/// A percent parameter `name` or `name:upper`, `None` for an unknown transform.
+fn percent_param(inner: &str, p_type: ParamType) -> Option<Piece<'_>> {
+    match inner.split_once(':') {
+        Some((name, transform)) => {
+            Transform::from_name(transform).map(|t| Piece::Param(name, p_type, Some(t)))
+        }
+        None => Some(Piece::Param(inner, p_type, None)),
+    }
+}
+
+/// `%{name}`, `%{name:upper}`, `*{name}` and `@{key}`, escapes: `%%{`, `**{` and `@@{`.
+fn scan_percent<'b>(msg: &'b str, r: &mut Vec<Piece<'b>>) -> bool {
+    let bytes = msg.as_bytes();
+    let mut pos = 0;
+    let mut start = 0;
+    // the sigil of the current parameter
+    let mut in_param = None;
+    let mut ok = true;
+
+    while pos < bytes.len() {
+        let rest = &bytes[pos..];
+        let sigil = matches!(rest[0], b'%' | b'*' | b'@');
+        if in_param.is_none()
+            && sigil
+            && rest.get(1) == Some(&rest[0])
+            && rest.get(2) == Some(&b'{')
+        {
+            // escaped `%{`, `*{` or `@{`: keep the first char and skip the second
+            push_text(r, &msg[start..=pos]);
+            pos += 3;
+            start = pos - 1;
+        } else if sigil && rest.get(1) == Some(&b'{') {
+            if in_param.is_some() {
+                ok = false;
+            } else {
+                push_text(r, &msg[start..pos]);
+                in_param = Some(rest[0]);
+                start = pos + 2;
+            }
+            pos += 2;
+        } else if rest[0] == b'}' && in_param.is_some() {
+            let name = &msg[start..pos];
+            let piece = match in_param {
+                Some(b'%') => percent_param(name, ParamType::Str),
+                Some(b'*') => percent_param(name, ParamType::Typed),
+                _ => Some(Piece::Ref(name)),
+            };
+            if let Some(piece) = piece {
+                r.push(piece);
+            } else {
+                ok = false;
+            }
+            in_param = None;
+            pos += 1;
+            start = pos;
+        } else {
+            pos += 1;
+        }
+    }
+
+    if in_param.is_some() {
+        ok = false;
+    } else {
+        push_text(r, &msg[start..]);
+    }
+
+    ok
+}
+
+/// `{name}` and `{name, typed}`, escapes: `''` and quoted text starting with `'{` or `'}`.
+fn scan_icu<'b>(msg: &'b str, r: &mut Vec<Piece<'b>>) -> bool {
+    let bytes = msg.as_bytes();
+    let mut pos = 0;
+    let mut start = 0;
+
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\'' if bytes.get(pos + 1) == Some(&b'\'') => {
+                push_text(r, &msg[start..=pos]);
+                pos += 2;
+                start = pos;
+            }
+            b'\'' if matches!(bytes.get(pos + 1), Some(b'{' | b'}')) => {
+                // quoted text, ends at the next single `'` (or the end of the message)
+                push_text(r, &msg[start..pos]);
+                pos += 1;
+                start = pos;
+                while pos < bytes.len() {
+                    if bytes[pos] == b'\'' {
+                        if bytes.get(pos + 1) == Some(&b'\'') {
+                            push_text(r, &msg[start..=pos]);
+                            pos += 2;
+                            start = pos;
+                        } else {
+                            push_text(r, &msg[start..pos]);
+                            pos += 1;
+                            start = pos;
+                            break;
+                        }
+                    } else {
+                        pos += 1;
+                    }
+                }
+            }
+            b'{' => {
+                push_text(r, &msg[start..pos]);
+                if let Some(end) = msg[pos + 1..].find('}') {
+                    r.push(param(&msg[pos + 1..pos + 1 + end]));
+                    pos += end + 2;
+                    start = pos;
+                } else {
+                    return false;
+                }
+            }
+            _ => pos += 1,
+        }
+    }
+    push_text(r, &msg[start..]);
+
+    true
+}
+
+/// `{{name}}`, `{{- name}}`, `{{name, typed}}` and `$t(key)`.
+fn scan_i18next<'b>(msg: &'b str, r: &mut Vec<Piece<'b>>) -> bool {
+    let mut rest = msg;
+    loop {
+        let param_begin = rest.find("{{");
+        let ref_begin = rest.find("$t(");
+        if let Some(begin) = ref_begin.filter(|rb| param_begin.map_or(true, |pb| rb < &pb)) {
+            push_text(r, &rest[..begin]);
+            if let Some(end) = rest[begin + 3..].find(')') {
+                r.push(Piece::Ref(rest[begin + 3..begin + 3 + end].trim()));
+                rest = &rest[begin + end + 4..];
+            } else {
+                return false;
+            }
+        } else if let Some(begin) = param_begin {
+            push_text(r, &rest[..begin]);
+            if let Some(end) = rest[begin + 2..].find("}}") {
+                let inner = &rest[begin + 2..begin + 2 + end];
+                let inner = inner.trim_start();
+                r.push(param(inner.strip_prefix('-').unwrap_or(inner)));
+                rest = &rest[begin + end + 4..];
+            } else {
+                return false;
+            }
+        } else {
+            break;
+        }
+    }
+    push_text(r, rest);
+
+    true
+}
@@ -1,85 +1,481 @@
+use crate::attribute::Parameters;
 use crate::diagnostic::Diagnostic;
 use crate::languages::Languages;
 use crate::messages::message::Message;
 use crate::messages::message_line::MessageLine;
 use crate::messages::messages::Messages;
+use crate::messages::metadata::{LengthUnit, MaxLength, Metadata};
 use crate::messages::param_type::ParamType;
 use crate::messages::piece::Piece;
+use crate::messages::placeholders::scan_markup;
+use crate::messages::serde::SerdeInput;
+use crate::messages::transform::Transform;
 use indexmap::IndexMap;
 use proc_macro2::{Ident, Span};
 use std::borrow::Cow;
-use std::collections::HashSet;
-use std::str::from_utf8_unchecked;
+use std::collections::{BTreeSet, HashSet};
+use std::ffi::OsStr;
+use std::path::Path;
 
-pub(crate) struct RawMessages<'a>(
+pub(crate) struct RawMessages<'a>(pub(crate) IndexMap<Cow<'a, str>, RawMessage<'a>>);
+
+pub(crate) struct RawMessage<'a> {
+    pub(crate) path: Vec<Cow<'a, str>>,
+    /// The declared parameters (`_params`).
+    pub(crate) params: Option<Vec<Cow<'a, str>>>,
+    /// The case transform of the message (`_transform`).
+    pub(crate) transform: Option<Transform>,
+    /// The type of a constant (`_type`).
+    pub(crate) constant_type: Option<Cow<'a, str>>,
+    /// The information for the translators.
+    pub(crate) metadata: Metadata<'a>,
+    /// The note of a deprecated message (`_deprecated`), empty if there is none.
+    pub(crate) deprecated: Option<Cow<'a, str>>,
+    /// The old keys of the message (`_aliases`).
+    pub(crate) aliases: Vec<Cow<'a, str>>,
+    pub(crate) values: IndexMap<Cow<'a, str>, Cow<'a, str>>,
+    /// The integers and booleans per language, only valid for a constant.
+    pub(crate) scalars: IndexMap<Cow<'a, str>, Cow<'a, str>>,
+    /// The variants of a term per language.
     #[allow(clippy::type_complexity)]
-    pub(crate)  IndexMap<Cow<'a, str>, (Vec<Cow<'a, str>>, IndexMap<Cow<'a, str>, Cow<'a, str>>)>,
-);
+    pub(crate) variants: IndexMap<Cow<'a, str>, IndexMap<Cow<'a, str>, Cow<'a, str>>>,
+    /// The items of a list per language.
+    pub(crate) lists: IndexMap<Cow<'a, str>, Vec<Cow<'a, str>>>,
+    /// The key of the compound message this message is part of.
+    pub(crate) compound: Option<Cow<'a, str>>,
+}
+
+/// The value of a metadata entry (keys starting with `_`).
+pub(crate) enum RawMeta<'a> {
+    String(Cow<'a, str>),
+    List(Vec<Cow<'a, str>>),
+    Int(i64),
+    Bool(bool),
+}
+
+impl<'a> RawMeta<'a> {
+    /// A list, a string is split at whitespace and/or commas.
+    fn into_list(self) -> Option<Vec<Cow<'a, str>>> {
+        match self {
+            RawMeta::String(Cow::Borrowed(s)) => Some(split_list(s).map(Cow::Borrowed).collect()),
+            RawMeta::String(Cow::Owned(s)) => {
+                Some(split_list(&s).map(|s| Cow::Owned(s.to_string())).collect())
+            }
+            RawMeta::List(l) => Some(l),
+            RawMeta::Int(_) | RawMeta::Bool(_) => None,
+        }
+    }
+
+    fn into_string(self) -> Option<Cow<'a, str>> {
+        match self {
+            RawMeta::String(s) => Some(s),
+            RawMeta::List(_) | RawMeta::Int(_) | RawMeta::Bool(_) => None,
+        }
+    }
+
+    /// A length, a number is in chars.
+    pub(crate) fn into_max_length(self) -> Option<MaxLength> {
+        match self {
+            RawMeta::String(s) => MaxLength::parse(&s),
+            RawMeta::Int(i) => usize::try_from(i).ok().map(|length| MaxLength {
+                length,
+                unit: LengthUnit::Chars,
+            }),
+            RawMeta::List(_) | RawMeta::Bool(_) => None,
+        }
+    }
+}
+
+/// Split `lang:variant`.
+fn split_variant(lang: Cow<'_, str>) -> Option<(Cow<'_, str>, Cow<'_, str>)> {
+    match lang {
+        Cow::Borrowed(l) => l
+            .split_once(':')
+            .map(|(l, v)| (Cow::Borrowed(l), Cow::Borrowed(v))),
+        Cow::Owned(l) => l
+            .split_once(':')
+            .map(|(l, v)| (Cow::Owned(l.to_string()), Cow::Owned(v.to_string()))),
+    }
+}
+
+fn split_list(s: &str) -> impl Iterator<Item = &str> {
+    s.split(|s: char| s.is_whitespace() || s == ',')
+        .filter(|s| !s.is_empty())
+}
+
+impl<'a> RawMessage<'a> {
+    pub(crate) fn new(path: Vec<Cow<'a, str>>) -> Self {
+        RawMessage {
+            path,
+            params: None,
+            transform: None,
+            constant_type: None,
+            metadata: Metadata::default(),
+            deprecated: None,
+            aliases: Vec::new(),
+            values: IndexMap::new(),
+            scalars: IndexMap::new(),
+            variants: IndexMap::new(),
+            lists: IndexMap::new(),
+            compound: None,
+        }
+    }
+
+    /// Add the text of a language (`lang`) or of a variant (`lang:variant`), returns `false` if it's a duplicate.
+    pub(crate) fn insert<D: Diagnostic>(
+        &mut self,
+        diagnostic: &mut D,
+        span: Span,
+        key: &str,
+        lang: Cow<'a, str>,
+        text: Cow<'a, str>,
+    ) -> bool {
+        if let Some((l, v)) = split_variant(lang.clone()) {
+            if !key.starts_with('-') {
+                diagnostic.emit_error(
+                    span,
+                    format!("language {lang} key {key}: only terms can have variants"),
+                );
+                true
+            } else if self.values.contains_key(&l) {
+                diagnostic.emit_error(
+                    span,
+                    format!("language {l} key {key} has a text and variants"),
+                );
+                true
+            } else {
+                self.variants
+                    .entry(l)
+                    .or_default()
+                    .insert(v, text)
+                    .is_none()
+            }
+        } else if self.variants.contains_key(&lang) {
+            diagnostic.emit_error(
+                span,
+                format!("language {lang} key {key} has a text and variants"),
+            );
+            true
+        } else {
+            self.values.insert(lang, text).is_none()
+        }
+    }
+
+    /// Set a metadata entry (`name` starts with `_`).
+    pub(crate) fn set_meta<D: Diagnostic>(
+        &mut self,
+        diagnostic: &mut D,
+        span: Span,
+        key: &str,
+        name: &str,
+        value: RawMeta<'a>,
+    ) {
+        let metadata = &mut self.metadata;
+        // `None` if the value has the wrong type
+        let duplicate = match name {
+            "_params" => value.into_list().map(|l| self.params.replace(l).is_some()),
+            "_transform" => value.into_string().map(|t| {
+                if let Some(case) = Transform::from_name(&t) {
+                    self.transform.replace(case).is_some()
+                } else {
+                    diagnostic.emit_error(span, format!("unknown transform {t} in key {key}"));
+                    false
+                }
+            }),
+            "_type" => value
+                .into_string()
+                .map(|t| self.constant_type.replace(t).is_some()),
+            "_description" => value
+                .into_string()
+                .map(|d| metadata.description.replace(d).is_some()),
+            "_note" => value
+                .into_string()
+                .map(|n| metadata.note.replace(n).is_some()),
+            "_tags" => value.into_list().map(|t| {
+                let duplicate = !metadata.tags.is_empty();
+                metadata.tags = t;
+                duplicate
+            }),
+            "_deprecated" => match value {
+                RawMeta::String(n) => Some(self.deprecated.replace(n).is_some()),
+                RawMeta::Bool(true) => Some(self.deprecated.replace(Cow::Borrowed("")).is_some()),
+                RawMeta::Bool(false) => Some(false),
+                RawMeta::List(_) | RawMeta::Int(_) => None,
+            },
+            "_aliases" => value.into_list().map(|a| {
+                let duplicate = !self.aliases.is_empty();
+                self.aliases = a;
+                duplicate
+            }),
+            "_max_length" => {
+                if let Some(m) = value.into_max_length() {
+                    Some(metadata.max_length.replace(m).is_some())
+                } else {
+                    diagnostic.emit_error(
+                        span,
+                        format!("metadata {name} in key {key} is not a valid length"),
+                    );
+                    Some(false)
+                }
+            }
+            _ => {
+                diagnostic.emit_error(span, format!("unknown metadata {name} in key {key}"));
+                Some(false)
+            }
+        };
+        let duplicate = duplicate.unwrap_or_else(|| {
+            let kind = if name == "_params" || name == "_tags" || name == "_aliases" {
+                "a list"
+            } else {
+                "a string"
+            };
+            diagnostic.emit_error(span, format!("metadata {name} in key {key} is not {kind}"));
+            false
+        });
+        if duplicate {
+            diagnostic.emit_error(span, format!("duplicate metadata {name} in key {key}"));
+        }
+    }
+}
 
 impl<'a> RawMessages<'a> {
+    /// Parse the file, the format is selected by the extension of the filename.
+    pub(crate) fn parse<D: Diagnostic>(
+        diagnostic: &mut D,
+        span: Span,
+        parameters: &Parameters,
+        content: &'a str,
+    ) -> Self {
+        match Path::new(&parameters.filename)
+            .extension()
+            .and_then(OsStr::to_str)
+        {
+            Some("lrc") => RawMessages::parse_lrc(diagnostic, span, content),
+            Some("yaml") => match serde_yaml::from_str::<SerdeInput>(content) {
+                Ok(key_map) => {
+                    RawMessages::parse_serde(diagnostic, span, &parameters.separator, key_map)
+                }
+                Err(err) => {
+                    diagnostic.emit_error(span, format!("Invalid YAML format, {err}"));
+                    RawMessages(IndexMap::default())
+                }
+            },
+            Some("json") => match serde_json::from_str::<SerdeInput>(content) {
+                Ok(key_map) => {
+                    RawMessages::parse_serde(diagnostic, span, &parameters.separator, key_map)
+                }
+                Err(err) => {
+                    diagnostic.emit_error(span, format!("Invalid JSON format, {err}"));
+                    RawMessages(IndexMap::default())
+                }
+            },
+            Some(ext) => {
+                diagnostic.emit_error(span, format!("Unsupported file extension {ext:?}"));
+                RawMessages(IndexMap::default())
+            }
+            None => {
+                diagnostic.emit_error(
+                    span,
+                    format!("No file extension on {:?}", &parameters.filename),
+                );
+                RawMessages(IndexMap::default())
+            }
+        }
+    }
+
+    /// The names of all languages in order of their first use.
+    pub(crate) fn language_names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        for m in self.0.values() {
+            let langs = m
+                .values
+                .keys()
+                .chain(m.scalars.keys())
+                .chain(m.variants.keys())
+                .chain(m.lists.keys());
+            for lang in langs {
+                if !names.contains(&lang.as_ref()) {
+                    names.push(lang.as_ref());
+                }
+            }
+        }
+        names
+    }
+
     pub(crate) fn parse_values<'b: 'a, D: Diagnostic>(
         diagnostic: &mut D,
         span: Span,
+        parameters: &Parameters,
         messages: RawMessages<'b>,
         languages: &Languages,
     ) -> Messages<'b> {
         let inner = messages
             .0
             .into_iter()
-            .filter_map(|(k, (path, v))| {
-                Self::parse_message(diagnostic, span, languages, k, path, v)
+            .filter_map(|(k, raw)| {
+                Self::parse_message(diagnostic, span, parameters, languages, k, raw)
             })
             .collect::<IndexMap<_, _>>();
         if inner.is_empty() {
             diagnostic.emit_error(span, "no messages found");
             diagnostic.should_abort_if_dirty();
         }
-        Messages::new(inner)
+        let mut messages = Messages::new(inner, &parameters.separator);
+        messages.resolve_references(diagnostic, span);
+        messages.collect_compounds(diagnostic, span);
+        messages.check_aliases(diagnostic, span);
+        // the generator requires that all references are valid
+        diagnostic.should_abort_if_dirty();
+        messages.check_max_lengths(diagnostic, span, parameters, languages);
+        messages.check_charsets(diagnostic, span, languages);
+        messages.check_completeness(diagnostic, parameters, languages);
+        messages
     }
 
     fn parse_message<'b, D: Diagnostic>(
         diagnostic: &mut D,
         span: Span,
+        parameters: &Parameters,
         languages: &Languages,
         k: Cow<'b, str>,
-        path: Vec<Cow<'b, str>>,
-        v: IndexMap<Cow<'b, str>, Cow<'b, str>>,
+        raw: RawMessage<'b>,
     ) -> Option<(Cow<'b, str>, Message<'b>)> {
-        let mut v_new = IndexMap::new();
+        let RawMessage {
+            path,
+            params: declared_params,
+            transform,
+            constant_type,
+            metadata,
+            deprecated,
+            aliases,
+            values,
+            scalars,
+            variants,
+            lists,
+            compound,
+        } = raw;
+        if let Some(constant_type) = constant_type {
+            let message = Message {
+                path,
+                params: Vec::new(),
+                declared_params,
+                transform,
+                constant_type: Some(constant_type),
+                metadata,
+                deprecated,
+                aliases,
+                message_lines: IndexMap::new(),
+                variants: IndexMap::new(),
+                lists: IndexMap::new(),
+                constants: IndexMap::new(),
+                compound,
+            };
+            let has_texts = !variants.is_empty() || !lists.is_empty();
+            return Self::parse_constant(
+                diagnostic,
+                span,
+                languages,
+                k,
+                message,
+                values.into_iter().chain(scalars),
+                has_texts,
+            );
+        }
+        for lang in scalars.keys() {
+            diagnostic.emit_error(span, format!("language {lang} key {k} is not a string"));
+        }
         let mut params = Vec::new();
-        for (lang, msg) in v {
+        let mut tag_sets = Vec::new();
+        let mut parse_line = |lang: &str, label: &str, msg: Cow<'b, str>| {
             if !languages.iter().any(|l| l.name == lang) {
                 diagnostic.emit_error(span, format!("language {lang} key {k} is not known"));
-                continue;
+                return None;
             }
-            let msg_line = MessageLine::build(msg, |msg| {
-                let pieces = Self::parse_value(diagnostic, span, &k, &lang, msg);
+            Some(MessageLine::build(msg, |msg| {
+                let pieces = Self::parse_value(diagnostic, span, parameters, &k, label, msg);
+                let mut tags = BTreeSet::new();
                 for p in &pieces {
-                    match p {
-                        Piece::Text(_) => {}
-                        Piece::Param(p_name, p_type) => {
-                            let p_name = *p_name;
-                            let p_type = *p_type;
-                            if let Some((_, old_p_type)) = params.iter().find(|(x, _)| *x == p_name)
-                            {
-                                if p_type != *old_p_type {
-                                    diagnostic.emit_error(
-                                            span,
-                                            format!("mismatching types for parameter {p_name} in key {lang}")
-                                        );
-                                }
-                            } else {
-                                params.push((p_name.to_string(), p_type));
+                    let param = match p {
+                        Piece::Text(_) | Piece::Ref(_) | Piece::TagClose(_) => None,
+                        Piece::Param(p_name, p_type, _) => Some((*p_name, *p_type)),
+                        Piece::TagOpen(p_name) => {
+                            tags.insert((*p_name).to_string());
+                            Some((*p_name, ParamType::Tag))
+                        }
+                    };
+                    if let Some((p_name, p_type)) = param {
+                        if let Some(declared_params) = &declared_params {
+                            if !declared_params.iter().any(|d| d == p_name) {
+                                diagnostic.emit_error(
+                                    span,
+                                    format!("undeclared parameter {p_name} in {k}.{label}"),
+                                );
+                            }
+                        }
+                        if let Some((_, old_p_type)) = params.iter().find(|(x, _)| *x == p_name) {
+                            if p_type != *old_p_type {
+                                diagnostic.emit_error(
+                                    span,
+                                    format!(
+                                        "mismatching types for parameter {p_name} in key {label}"
+                                    ),
+                                );
                             }
+                        } else {
+                            params.push((p_name.to_string(), p_type));
                         }
                     }
                 }
+                tag_sets.push((label.to_string(), tags));
                 pieces
-            });
+            }))
+        };
 
-            v_new.insert(lang, msg_line);
+        let mut v_new = IndexMap::new();
+        for (lang, msg) in values {
+            if let Some(msg_line) = parse_line(&lang, &lang, msg) {
+                v_new.insert(lang, msg_line);
+            }
+        }
+        let mut variants_new = IndexMap::new();
+        for (lang, variant_map) in variants {
+            let mut language_variants = IndexMap::new();
+            for (variant, msg) in variant_map {
+                if let Some(msg_line) = parse_line(&lang, &format!("{lang}:{variant}"), msg) {
+                    language_variants.insert(variant, msg_line);
+                }
+            }
+            if !language_variants.is_empty() {
+                variants_new.insert(lang, language_variants);
+            }
+        }
+        let mut lists_new = IndexMap::new();
+        for (lang, items) in lists {
+            let mut lines = Vec::new();
+            for (pos, msg) in items.into_iter().enumerate() {
+                if let Some(msg_line) = parse_line(&lang, &format!("{lang}[{pos}]"), msg) {
+                    lines.push(msg_line);
+                }
+            }
+            lists_new.insert(lang, lines);
         }
-        if v_new.is_empty() {
+        if !lists_new.is_empty() {
+            if !v_new.is_empty() || !variants_new.is_empty() {
+                diagnostic.emit_error(span, format!("key {k} has texts and lists"));
+            }
+            Self::check_list_lengths(diagnostic, span, languages, &k, &lists_new);
+        }
+        if let Some(((first_label, first_tags), others)) = tag_sets.split_first() {
+            for (label, tags) in others {
+                if tags != first_tags {
+                    diagnostic.emit_error(
+                        span,
+                        format!("the tags of {k}.{label} differ from {k}.{first_label}"),
+                    );
+                }
+            }
+        }
+        if v_new.is_empty() && variants_new.is_empty() && lists_new.is_empty() {
             diagnostic.emit_error(span, format!("key {k} has no values"));
             None
         } else {
@@ -88,88 +484,142 @@ impl<'a> RawMessages<'a> {
                 Message {
                     path,
                     params,
+                    declared_params,
+                    transform,
+                    constant_type: None,
+                    metadata,
+                    deprecated,
+                    aliases,
                     message_lines: v_new,
+                    variants: variants_new,
+                    lists: lists_new,
+                    constants: IndexMap::new(),
+                    compound,
                 },
             ))
         }
     }
 
+    /// All lists must have the length of the list of the source (default) language.
+    fn check_list_lengths<D: Diagnostic>(
+        diagnostic: &mut D,
+        span: Span,
+        languages: &Languages,
+        k: &str,
+        lists: &IndexMap<Cow<'_, str>, Vec<MessageLine<'_>>>,
+    ) {
+        let source = languages
+            .default_language()
+            .fallback
+            .iter()
+            .find_map(|l| lists.get_key_value(l.as_str()));
+        if let Some((source_lang, source_list)) = source {
+            for (lang, list) in lists {
+                if list.len() != source_list.len() {
+                    diagnostic.emit_error(
+                        span,
+                        format!(
+                            "list {k}.{lang} has {} items, but {k}.{source_lang} has {}",
+                            list.len(),
+                            source_list.len()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Sort the used parameters into the order of the declaration.
+    pub(crate) fn order_params<D: Diagnostic>(
+        diagnostic: &mut D,
+        span: Span,
+        k: &str,
+        declared_params: &[Cow<'_, str>],
+        params: &[(String, ParamType)],
+    ) -> Vec<(String, ParamType)> {
+        let mut ordered = Vec::new();
+        for (pos, d) in declared_params.iter().enumerate() {
+            if syn::parse_str::<Ident>(d).is_err() {
+                diagnostic.emit_error(
+                    span,
+                    format!(r#"invalid declared parameter name "{d}" in key {k}"#),
+                );
+            } else if declared_params[..pos].contains(d) {
+                diagnostic.emit_error(span, format!("duplicate declared parameter {d} in key {k}"));
+            } else if let Some(p) = params.iter().find(|(p, _)| p == d) {
+                ordered.push(p.clone());
+            } else {
+                diagnostic.emit_error(
+                    span,
+                    format!("declared parameter {d} is not used in key {k}"),
+                );
+            }
+        }
+        ordered
+    }
+
     fn parse_value<'b, D: Diagnostic>(
         diagnostic: &mut D,
         span: Span,
+        parameters: &Parameters,
         k: &str,
         lang: &str,
         msg: &'b str,
     ) -> Vec<Piece<'b>> {
         let mut r = Vec::new();
-        let mut t_params = HashSet::new();
-        let msg = msg.as_bytes();
-        let mut pos = 0;
-        let mut start = 0;
-        let mut in_param = false;
-        let mut param_type = ParamType::Str;
-        let mut has_error = false;
-
-        while pos < msg.len() {
-            if pos + 1 < msg.len() && (msg[pos] == b'%' || msg[pos] == b'*') && msg[pos + 1] == b'{'
-            {
-                if in_param {
-                    has_error = true;
-                    pos += 2;
-                } else {
-                    if pos > start {
-                        // this is safe because it's only cut on ascii chars
-                        r.push(Piece::Text(unsafe {
-                            from_utf8_unchecked(&msg[start..pos])
-                        }));
+        if !parameters.placeholders.scan(msg, &mut r) {
+            diagnostic.emit_error(span, format!("parse error in {k}.{lang}"));
+        }
+        if parameters.markup {
+            r = scan_markup(r);
+            let mut open = Vec::new();
+            let mut balanced = true;
+            for p in &r {
+                match p {
+                    Piece::TagOpen(t) => open.push(*t),
+                    Piece::TagClose(t) if open.pop() != Some(*t) => {
+                        diagnostic.emit_error(span, format!("unbalanced tag {t} in {k}.{lang}"));
+                        balanced = false;
+                        break;
                     }
-                    in_param = true;
-                    param_type = if msg[pos] == b'%' {
-                        ParamType::Str
-                    } else {
-                        ParamType::Typed
-                    };
-                    pos += 2;
-                    start = pos;
+                    _ => {}
                 }
-            } else if msg[pos] == b'}' && in_param {
-                // this is safe because it's only cut on ascii chars
-                let p_name = unsafe { from_utf8_unchecked(&msg[start..pos]) };
+            }
+            if let (true, Some(t)) = (balanced, open.first()) {
+                diagnostic.emit_error(span, format!("unclosed tag {t} in {k}.{lang}"));
+            }
+        }
+
+        let mut t_params = HashSet::new();
+        r.retain(|p| match p {
+            Piece::Text(_) | Piece::Ref(_) | Piece::TagClose(_) => true,
+            Piece::Param(p_name, ParamType::Typed, Some(_)) => {
+                diagnostic.emit_error(
+                    span,
+                    format!("only str parameters can be transformed: {p_name} in {k}.{lang}"),
+                );
+                false
+            }
+            Piece::Param(p_name, _, _) | Piece::TagOpen(p_name) => {
                 if syn::parse_str::<Ident>(p_name).is_err() {
                     diagnostic.emit_error(
                         span,
                         format!(r#"invalid parameter name "{p_name}" in {k}.{lang}"#),
                     );
-                } else if !t_params.insert(p_name) {
+                    false
+                } else if !t_params.insert(*p_name) {
                     diagnostic.emit_error(
                         span,
                         format!(
                             r#"duplicate use of a typed parameter: "{p_name}" in key {k}.{lang}"#
                         ),
                     );
+                    false
                 } else {
-                    r.push(Piece::Param(p_name, param_type));
+                    true
                 }
-                in_param = false;
-                pos += 1;
-                start = pos;
-            } else {
-                pos += 1;
             }
-        }
-
-        if in_param {
-            has_error = true;
-        } else if pos > start {
-            // this is safe because it's only cut on ascii chars
-            r.push(Piece::Text(unsafe {
-                from_utf8_unchecked(&msg[start..pos])
-            }));
-        }
-
-        if has_error {
-            diagnostic.emit_error(span, format!("parse error in {k}.{lang}"));
-        }
+        });
 
         r
     }
@@ -0,0 +1,308 @@
+use crate::diagnostic::Diagnostic;
+use crate::languages::Language;
+use crate::messages::message::Message;
+use crate::messages::message_line::MessageLine;
+use crate::messages::messages::Messages;
+use crate::messages::param_type::ParamType;
+use crate::messages::piece::Piece;
+use crate::messages::raw::RawMessages;
+use proc_macro2::Span;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+type Params = Vec<(String, ParamType)>;
+
+impl<'a> Messages<'a> {
+    /// Find a referenced message or term, a `.` may be used instead of the separator.
+    ///
+    /// A term may be referenced without the leading `-`.
+    pub(crate) fn get_reference(&self, name: &str) -> Option<(&str, &Message<'a>)> {
+        self.inner
+            .get_key_value(name)
+            .or_else(|| {
+                self.inner
+                    .get_key_value(name.replace('.', &self.separator).as_str())
+            })
+            .or_else(|| self.inner.get_key_value(format!("-{name}").as_str()))
+            .map(|(k, m)| (k.as_ref(), m))
+    }
+
+    /// Append the pieces to `out`, all references are (recursively) replaced by their text
+    /// in the language with the `fallback`.
+    ///
+    /// Returns `false` for an unknown, missing or cyclic reference (reported by
+    /// `resolve_references`), then `out` is incomplete.
+    #[must_use]
+    pub(crate) fn flatten<'s>(
+        &'s self,
+        fallback: &[String],
+        pieces: &[Piece<'s>],
+        out: &mut Vec<Piece<'s>>,
+    ) -> bool {
+        self.flatten_checked(fallback, pieces, out, &mut Vec::new())
+    }
+
+    /// The texts which the language displays for the message (with the fallback), each with a
+    /// label (`lang`, `lang[pos]` or `lang:variant`) and the flattened pieces.
+    ///
+    /// The texts with invalid references (reported by `resolve_references`) are skipped.
+    pub(crate) fn displayed_lines<'s>(
+        &'s self,
+        language: &Language,
+        message: &'s Message<'a>,
+    ) -> Vec<(String, Vec<Piece<'s>>)> {
+        let lang = if let Some(lang) = language
+            .fallback
+            .iter()
+            .find(|f| message.has_language(f) || message.variants.contains_key(f.as_str()))
+        {
+            lang.as_str()
+        } else {
+            return Vec::new();
+        };
+        let name = &language.name;
+        let suffix = if lang == name {
+            String::new()
+        } else {
+            format!(" (fallback {lang})")
+        };
+        let mut lines = Vec::new();
+        if let Some(ml) = message.message_lines.get(lang) {
+            lines.push((format!("{name}{suffix}"), ml));
+        }
+        if let Some(list) = message.lists.get(lang) {
+            for (pos, ml) in list.iter().enumerate() {
+                lines.push((format!("{name}[{pos}]{suffix}"), ml));
+            }
+        }
+        if let Some(variants) = message.variants.get(lang) {
+            for (variant, ml) in variants {
+                lines.push((format!("{name}:{variant}{suffix}"), ml));
+            }
+        }
+        lines
+            .into_iter()
+            .filter_map(|(label, ml)| {
+                let mut pieces = Vec::new();
+                self.flatten(&language.fallback, ml.borrow_pieces(), &mut pieces)
+                    .then_some((label, pieces))
+            })
+            .collect()
+    }
+
+    fn flatten_checked<'s>(
+        &'s self,
+        fallback: &[String],
+        pieces: &[Piece<'s>],
+        out: &mut Vec<Piece<'s>>,
+        stack: &mut Vec<&'s str>,
+    ) -> bool {
+        for p in pieces {
+            if let Piece::Ref(r) = p {
+                let (r, variant) = split_reference(r);
+                let (r_key, message) = if let Some(r) = self.get_reference(r) {
+                    r
+                } else {
+                    return false;
+                };
+                let line = fallback.iter().find_map(|l| message.line(l, variant));
+                let line = match line {
+                    Some(line) if !stack.contains(&r_key) => line,
+                    _ => return false,
+                };
+                stack.push(r_key);
+                let valid = self.flatten_checked(fallback, line.borrow_pieces(), out, stack);
+                stack.pop();
+                if !valid {
+                    return false;
+                }
+            } else {
+                out.push(*p);
+            }
+        }
+        true
+    }
+
+    /// Check all references and merge the parameters of the referenced messages.
+    pub(crate) fn resolve_references<D: Diagnostic>(&mut self, diagnostic: &mut D, span: Span) {
+        let mut resolved = HashMap::new();
+        for k in self.inner.keys() {
+            self.resolve_params(diagnostic, span, k, &mut Vec::new(), &mut resolved);
+        }
+        for (k, m) in &mut self.inner {
+            let params = resolved.remove(k.as_ref()).unwrap_or_default();
+            m.params = if let Some(declared_params) = &m.declared_params {
+                RawMessages::order_params(diagnostic, span, k, declared_params, &params)
+            } else {
+                params
+            };
+        }
+    }
+
+    fn resolve_params<D: Diagnostic>(
+        &self,
+        diagnostic: &mut D,
+        span: Span,
+        k: &str,
+        stack: &mut Vec<String>,
+        resolved: &mut HashMap<String, Params>,
+    ) -> Option<Params> {
+        if let Some(params) = resolved.get(k) {
+            return Some(params.clone());
+        }
+        if let Some(pos) = stack.iter().position(|s| s == k) {
+            diagnostic.emit_error(
+                span,
+                format!("reference cycle: {} -> {k}", stack[pos..].join(" -> ")),
+            );
+            return None;
+        }
+        let message = self.inner.get(k)?;
+        stack.push(k.to_string());
+        let mut params = message.params.clone();
+        for (lang, line) in message.lines() {
+            for p in line.borrow_pieces() {
+                if let Piece::Ref(r) = p {
+                    let (r, variant) = split_reference(r);
+                    if let Some((r_key, r_message)) = self.get_reference(r) {
+                        if !r_message.lists.is_empty() {
+                            diagnostic.emit_error(
+                                span,
+                                format!("reference to the list {r_key} in {k}.{lang}"),
+                            );
+                        }
+                        if r_message.constant_type.is_some() {
+                            diagnostic.emit_error(
+                                span,
+                                format!("reference to the constant {r_key} in {k}.{lang}"),
+                            );
+                        }
+                        if let Some(variant) = variant {
+                            Self::check_variant(
+                                diagnostic,
+                                span,
+                                (k, &lang, r_key),
+                                r_message,
+                                variant,
+                            );
+                        }
+                        let r_params = self
+                            .resolve_params(diagnostic, span, r_key, stack, resolved)
+                            .unwrap_or_default();
+                        for (p_name, p_type) in r_params {
+                            Self::merge_param(
+                                diagnostic,
+                                span,
+                                message,
+                                &mut params,
+                                (k, &lang, r),
+                                p_name,
+                                p_type,
+                            );
+                        }
+                    } else {
+                        diagnostic.emit_error(span, format!("unknown reference {r} in {k}.{lang}"));
+                    }
+                }
+            }
+        }
+        stack.pop();
+        resolved.insert(k.to_string(), params.clone());
+        Some(params)
+    }
+
+    fn check_variant<D: Diagnostic>(
+        diagnostic: &mut D,
+        span: Span,
+        (k, lang, r_key): (&str, &str, &str),
+        r_message: &Message,
+        variant: &str,
+    ) {
+        if !r_key.starts_with('-') {
+            diagnostic.emit_error(
+                span,
+                format!("only terms have variants, {r_key}:{variant} in {k}.{lang}"),
+            );
+        }
+        for (v_lang, variants) in &r_message.variants {
+            if !variants.contains_key(variant) {
+                diagnostic.emit_error(
+                    span,
+                    format!(
+                        "variant {variant} of term {r_key} is missing in {v_lang} (referenced in {k}.{lang})"
+                    ),
+                );
+            }
+        }
+    }
+
+    fn merge_param<D: Diagnostic>(
+        diagnostic: &mut D,
+        span: Span,
+        message: &Message,
+        params: &mut Params,
+        (k, lang, r): (&str, &str, &str),
+        p_name: String,
+        p_type: ParamType,
+    ) {
+        if let Some((_, old_p_type)) = params.iter().find(|(x, _)| *x == p_name) {
+            if p_type != *old_p_type {
+                diagnostic.emit_error(
+                    span,
+                    format!("mismatching types for parameter {p_name} in key {k}.{lang} (referenced {r})"),
+                );
+            }
+        } else {
+            if let Some(declared_params) = &message.declared_params {
+                if !declared_params.iter().any(|d| *d == p_name) {
+                    diagnostic.emit_error(
+                        span,
+                        format!("undeclared parameter {p_name} in {k}.{lang} (referenced {r})"),
+                    );
+                }
+            }
+            params.push((p_name, p_type));
+        }
+    }
+}
+
+/// Split `key:variant`.
+fn split_reference(r: &str) -> (&str, Option<&str>) {
+    match r.split_once(':') {
+        Some((r, variant)) => (r, Some(variant)),
+        None => (r, None),
+    }
+}
+
+impl<'a> Message<'a> {
+    /// The text of the language, the first variant is the default of a term, `None` for an
+    /// unknown variant.
+    fn line(&self, lang: &str, variant: Option<&str>) -> Option<&MessageLine<'a>> {
+        if let Some(ml) = self.message_lines.get(lang) {
+            Some(ml)
+        } else {
+            let variants = self.variants.get(lang)?;
+            variant.map_or_else(|| variants.first().map(|(_, ml)| ml), |v| variants.get(v))
+        }
+    }
+
+    /// All texts, the variants are labeled `lang:variant` and the list items `lang[pos]`.
+    pub(crate) fn lines(&self) -> Vec<(Cow<'_, str>, &MessageLine<'a>)> {
+        let mut lines = self
+            .message_lines
+            .iter()
+            .map(|(l, ml)| (Cow::Borrowed(l.as_ref()), ml))
+            .collect::<Vec<_>>();
+        for (l, vm) in &self.variants {
+            for (v, ml) in vm {
+                lines.push((Cow::Owned(format!("{l}:{v}")), ml));
+            }
+        }
+        for (l, list) in &self.lists {
+            for (pos, ml) in list.iter().enumerate() {
+                lines.push((Cow::Owned(format!("{l}[{pos}]")), ml));
+            }
+        }
+        lines
+    }
+}
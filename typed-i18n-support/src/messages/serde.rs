@@ -1,6 +1,8 @@
 use crate::diagnostic::Diagnostic;
+use crate::messages::message::Message;
 use crate::messages::messages::Messages;
-use crate::messages::raw::RawMessages;
+use crate::messages::metadata::{LengthUnit, MaxLength};
+use crate::messages::raw::{RawMessage, RawMessages, RawMeta};
 use crate::messages::MessagesAsTree;
 use indexmap::IndexMap;
 use proc_macro2::Span;
@@ -21,11 +23,28 @@ fn default_version() -> usize {
 #[derive(serde::Deserialize)]
 #[serde(untagged)]
 enum ObjectOrString<'a> {
-    LangMap(IndexMap<Cow<'a, str>, Cow<'a, str>>),
-    Depth(IndexMap<Cow<'a, str>, Box<ObjectOrString<'a>>>),
+    String(Cow<'a, str>),
+    List(Vec<Cow<'a, str>>),
+    Object(IndexMap<Cow<'a, str>, ObjectOrString<'a>>),
+    Bool(bool),
+    Int(i64),
 }
 
 impl<'a> ObjectOrString<'a> {
+    /// The value of a `_max_length`, a number is in chars.
+    fn max_length(&self) -> Option<MaxLength> {
+        match self {
+            ObjectOrString::String(s) => MaxLength::parse(s),
+            ObjectOrString::Int(i) => usize::try_from(*i).ok().map(|length| MaxLength {
+                length,
+                unit: LengthUnit::Chars,
+            }),
+            ObjectOrString::List(_) | ObjectOrString::Object(_) | ObjectOrString::Bool(_) => None,
+        }
+    }
+
+    /// `max_length` is the one of the enclosing subtree, it applies to all messages without an own one.
+    #[allow(clippy::too_many_arguments)]
     fn into_outer<D: Diagnostic>(
         self,
         diagnostic: &mut D,
@@ -33,28 +52,132 @@ impl<'a> ObjectOrString<'a> {
         separator: &str,
         result: &mut RawMessages<'a>,
         keys: &mut Vec<Cow<'a, str>>,
+        compound: Option<&Cow<'a, str>>,
+        max_length: Option<MaxLength>,
     ) {
-        match self {
-            ObjectOrString::LangMap(l) => {
-                let key = if keys.len() == 1 {
-                    keys[0].clone()
+        let key = if keys.len() == 1 {
+            keys[0].clone()
+        } else {
+            Cow::Owned(keys.join(separator))
+        };
+        let object = match self {
+            ObjectOrString::Object(o) => o,
+            ObjectOrString::String(_)
+            | ObjectOrString::List(_)
+            | ObjectOrString::Bool(_)
+            | ObjectOrString::Int(_) => {
+                diagnostic.emit_error(span, format!("key {key} is not an object"));
+                return;
+            }
+        };
+        // a term may have variants (objects) but no sub keys
+        let is_term = keys.len() == 1 && key.starts_with('-');
+        if !is_term
+            && object
+                .values()
+                .any(|v| matches!(v, ObjectOrString::Object(_)))
+        {
+            // sub keys
+            if let Some(compound) = compound {
+                diagnostic.emit_error(
+                    span,
+                    format!("compound {compound} contains the sub keys {key}"),
+                );
+                return;
+            }
+            let is_compound = matches!(object.get("_compound"), Some(ObjectOrString::Bool(true)));
+            let max_length = match object.get("_max_length") {
+                Some(m) => m.max_length().or_else(|| {
+                    diagnostic.emit_error(
+                        span,
+                        format!("metadata _max_length in key {key} is not a valid length"),
+                    );
+                    max_length
+                }),
+                None => max_length,
+            };
+            for (k, v) in object {
+                if matches!(v, ObjectOrString::Object(_)) {
+                    keys.push(k);
+                    v.into_outer(
+                        diagnostic,
+                        span,
+                        separator,
+                        result,
+                        keys,
+                        is_compound.then_some(&key),
+                        max_length,
+                    );
+                    keys.pop();
+                } else if (k == "_compound" && matches!(v, ObjectOrString::Bool(_)))
+                    || k == "_max_length"
+                {
+                    // the marker of a compound and the max length of the subtree, see above
                 } else {
-                    Cow::Owned(keys.join(separator))
-                };
-                if result.0.insert(key.clone(), (keys.clone(), l)).is_some() {
                     diagnostic.emit_error(
                         span,
-                        format!(r#"duplicate key "{key}", created from {keys:?}"#),
+                        format!("key {key} contains both sub keys and the entry {k}"),
                     );
                 }
             }
-            ObjectOrString::Depth(d) => {
-                for (k, v) in d {
-                    keys.push(k);
-                    v.into_outer(diagnostic, span, separator, result, keys);
-                    keys.pop();
+        } else {
+            // a message
+            let mut message = RawMessage::new(keys.clone());
+            message.compound = compound.cloned();
+            for (k, v) in object {
+                match v {
+                    ObjectOrString::String(s) if k.starts_with('_') => {
+                        message.set_meta(diagnostic, span, &key, &k, RawMeta::String(s));
+                    }
+                    ObjectOrString::List(l) if k.starts_with('_') => {
+                        message.set_meta(diagnostic, span, &key, &k, RawMeta::List(l));
+                    }
+                    ObjectOrString::String(s) => {
+                        message.values.insert(k, s);
+                    }
+                    ObjectOrString::Object(o) => {
+                        // the variants of a term
+                        let mut variants = IndexMap::new();
+                        for (variant, v) in o {
+                            if let ObjectOrString::String(s) = v {
+                                variants.insert(variant, s);
+                            } else {
+                                diagnostic.emit_error(
+                                    span,
+                                    format!("variant {variant} of language {k} key {key} is not a string"),
+                                );
+                            }
+                        }
+                        message.variants.insert(k, variants);
+                    }
+                    ObjectOrString::List(l) => {
+                        message.lists.insert(k, l);
+                    }
+                    ObjectOrString::Int(i) if k.starts_with('_') => {
+                        message.set_meta(diagnostic, span, &key, &k, RawMeta::Int(i));
+                    }
+                    ObjectOrString::Bool(b) if k.starts_with('_') => {
+                        message.set_meta(diagnostic, span, &key, &k, RawMeta::Bool(b));
+                    }
+                    ObjectOrString::Bool(b) => {
+                        message
+                            .scalars
+                            .insert(k, Cow::Borrowed(if b { "true" } else { "false" }));
+                    }
+                    ObjectOrString::Int(i) => {
+                        message.scalars.insert(k, Cow::Owned(i.to_string()));
+                    }
                 }
             }
+            if message.metadata.max_length.is_none() {
+                message.metadata.max_length = max_length;
+            }
+            if result.0.insert(key.clone(), message).is_some() {
+                diagnostic.emit_error(
+                    span,
+                    format!(r#"duplicate key "{key}", created from {keys:?}"#),
+                );
+            }
         }
     }
 }
@@ -71,7 +194,15 @@ impl<'a> RawMessages<'a> {
         }
         let mut result = RawMessages(IndexMap::new());
         key_map.inner.into_iter().for_each(|(k, v)| {
-            v.into_outer(diagnostic, span, separator, &mut result, &mut vec![k]);
+            v.into_outer(
+                diagnostic,
+                span,
+                separator,
+                &mut result,
+                &mut vec![k],
+                None,
+                None,
+            );
         });
 
         result
@@ -82,14 +213,18 @@ impl<'a> RawMessages<'a> {
 #[serde(untagged)]
 pub(super) enum MessagesTreeInner<'a> {
     Object(IndexMap<&'a str, Box<MessagesTreeInner<'a>>>),
-    String(&'a str),
+    Message(&'a Message<'a>),
+    /// The marker of a compound.
+    Flag(bool),
 }
 
 impl<'a> MessagesTreeInner<'a> {
     fn object(&mut self) -> &mut IndexMap<&'a str, Box<MessagesTreeInner<'a>>> {
         match self {
             MessagesTreeInner::Object(o) => o,
-            MessagesTreeInner::String(_) => panic!("called object() on a String"),
+            MessagesTreeInner::Message(_) | MessagesTreeInner::Flag(_) => {
+                panic!("called object() on a Message")
+            }
         }
     }
 }
@@ -98,17 +233,20 @@ impl<'a> Messages<'a> {
     #[must_use]
     pub fn as_tree(&'a self) -> MessagesAsTree<'a> {
         let mut inner: IndexMap<&str, Box<MessagesTreeInner>> = IndexMap::new();
-        for (_, m) in self {
+        for (k, m) in self {
             let mut ptr = &mut inner;
-            for p in m.path() {
+            let (last, path) = m.path.split_last().map_or((k, &[][..]), |(l, p)| (l, p));
+            for p in path {
                 ptr = ptr
                     .entry(p.as_ref())
                     .or_insert_with(|| Box::new(MessagesTreeInner::Object(IndexMap::new())))
                     .object();
             }
-            for (l, mkl) in &m.message_lines {
-                ptr.insert(l, Box::new(MessagesTreeInner::String(mkl.borrow_line())));
+            if m.compound.is_some() {
+                ptr.entry("_compound")
+                    .or_insert_with(|| Box::new(MessagesTreeInner::Flag(true)));
             }
+            ptr.insert(last, Box::new(MessagesTreeInner::Message(m)));
         }
 
         MessagesAsTree {
@@ -0,0 +1,216 @@
+use crate::messages::param_type::ParamType;
+use crate::messages::piece::Piece;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// A case transform of a parameter (`%{name:upper}`) or a message (`_transform`).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ::serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Transform {
+    Upper,
+    Lower,
+    Capitalize,
+}
+
+impl Transform {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "upper" => Some(Transform::Upper),
+            "lower" => Some(Transform::Lower),
+            "capitalize" => Some(Transform::Capitalize),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Transform::Upper => "upper",
+            Transform::Lower => "lower",
+            Transform::Capitalize => "capitalize",
+        }
+    }
+
+    /// The path of the runtime value.
+    pub(crate) fn tokens(self) -> TokenStream {
+        match self {
+            Transform::Upper => quote!(::typed_i18n::Case::Upper),
+            Transform::Lower => quote!(::typed_i18n::Case::Lower),
+            Transform::Capitalize => quote!(::typed_i18n::Case::Capitalize),
+        }
+    }
+
+    /// The transformed texts of the (flattened) pieces of a message, `None` for all other pieces.
+    pub(crate) fn message_texts(self, pieces: &[Piece<'_>], turkic: bool) -> Vec<Option<String>> {
+        let first = first_content(pieces);
+        pieces
+            .iter()
+            .enumerate()
+            .map(|(pos, p)| match p {
+                Piece::Text(t) if self != Transform::Capitalize || Some(pos) == first => {
+                    Some(self.map(t, turkic))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Apply the transform of a message: replace the texts (see [`Transform::message_texts`]) and
+    /// transform the str parameters without an own transform.
+    pub(crate) fn apply_message<'a>(
+        self,
+        pieces: &[Piece<'a>],
+        texts: &'a [Option<String>],
+    ) -> Vec<Piece<'a>> {
+        let first = first_content(pieces);
+        pieces
+            .iter()
+            .zip(texts)
+            .enumerate()
+            .map(|(pos, (p, t))| match (p, t) {
+                (_, Some(t)) => Piece::Text(t),
+                (Piece::Param(p_name, ParamType::Str, None), None)
+                    if self != Transform::Capitalize || Some(pos) == first =>
+                {
+                    Piece::Param(p_name, ParamType::Str, Some(self))
+                }
+                (p, None) => *p,
+            })
+            .collect()
+    }
+
+    /// Map the case of a constant text, the same as `typed_i18n::map_case` does at runtime.
+    pub(crate) fn map(self, text: &str, turkic: bool) -> String {
+        let mut r = String::with_capacity(text.len());
+        let mut previous_letter = false;
+        let mut capitalized = false;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match self {
+                Transform::Upper => upper(&mut r, c, turkic),
+                Transform::Lower => {
+                    if turkic && c == 'I' {
+                        r.push('ı');
+                    } else if turkic && c == 'İ' {
+                        r.push('i');
+                    } else if c == 'Σ' {
+                        // final sigma: after a letter and not before one
+                        let is_final =
+                            previous_letter && !chars.peek().map_or(false, |n| n.is_alphabetic());
+                        r.push(if is_final { 'ς' } else { 'σ' });
+                    } else {
+                        r.extend(c.to_lowercase());
+                    }
+                }
+                Transform::Capitalize => {
+                    if capitalized || !c.is_alphabetic() {
+                        r.push(c);
+                    } else {
+                        capitalized = true;
+                        if let Some((first, rest)) = titlecase(c) {
+                            r.push(first);
+                            r.push_str(rest);
+                        } else {
+                            upper(&mut r, c, turkic);
+                        }
+                    }
+                }
+            }
+            previous_letter = c.is_alphabetic();
+        }
+        r
+    }
+}
+
+fn upper(r: &mut String, c: char, turkic: bool) {
+    if turkic && c == 'i' {
+        r.push('İ');
+    } else {
+        r.extend(c.to_uppercase());
+    }
+}
+
+// the same as in typed-i18n/src/case.rs, which is checked by the test `same_titlecase`
+
+/// The titlecase of a char if it differs from the uppercase (as the first char and the rest),
+/// e.g. of digraphs, ligatures, Georgian and Greek with ypogegrammeni.
+fn titlecase(c: char) -> Option<(char, &'static str)> {
+    Some(match c {
+        'ß' => ('S', "s"),
+        'Ǆ' | 'ǅ' | 'ǆ' => ('ǅ', ""),
+        'Ǉ' | 'ǈ' | 'ǉ' => ('ǈ', ""),
+        'Ǌ' | 'ǋ' | 'ǌ' => ('ǋ', ""),
+        'Ǳ' | 'ǲ' | 'ǳ' => ('ǲ', ""),
+        'և' => ('Ե', "ւ"),
+        'ﬀ' => ('F', "f"),
+        'ﬁ' => ('F', "i"),
+        'ﬂ' => ('F', "l"),
+        'ﬃ' => ('F', "fi"),
+        'ﬄ' => ('F', "fl"),
+        'ﬅ' | 'ﬆ' => ('S', "t"),
+        'ﬓ' => ('Մ', "ն"),
+        'ﬔ' => ('Մ', "ե"),
+        'ﬕ' => ('Մ', "ի"),
+        'ﬖ' => ('Վ', "ն"),
+        'ﬗ' => ('Մ', "խ"),
+        '\u{1f80}'..='\u{1f87}' | '\u{1f90}'..='\u{1f97}' | '\u{1fa0}'..='\u{1fa7}' => {
+            (char::from_u32(u32::from(c) + 8)?, "")
+        }
+        // Georgian Mkhedruli and Greek with prosgegrammeni are their own titlecase
+        '\u{10d0}'..='\u{10fa}'
+        | '\u{10fd}'..='\u{10ff}'
+        | '\u{1f88}'..='\u{1f8f}'
+        | '\u{1f98}'..='\u{1f9f}'
+        | '\u{1fa8}'..='\u{1faf}' => (c, ""),
+        'ᾳ' | 'ᾼ' => ('ᾼ', ""),
+        'ῃ' | 'ῌ' => ('ῌ', ""),
+        'ῳ' | 'ῼ' => ('ῼ', ""),
+        'ᾲ' => ('\u{1fba}', "\u{345}"),
+        'ᾴ' => ('\u{386}', "\u{345}"),
+        'ᾷ' => ('\u{391}', "\u{342}\u{345}"),
+        'ῂ' => ('\u{1fca}', "\u{345}"),
+        'ῄ' => ('\u{389}', "\u{345}"),
+        'ῇ' => ('\u{397}', "\u{342}\u{345}"),
+        'ῲ' => ('\u{1ffa}', "\u{345}"),
+        'ῴ' => ('\u{38f}', "\u{345}"),
+        'ῷ' => ('\u{3a9}', "\u{342}\u{345}"),
+        _ => return None,
+    })
+}
+
+/// The position of the first piece which starts the visible text, i.e. a str parameter or a text with a letter.
+fn first_content(pieces: &[Piece<'_>]) -> Option<usize> {
+    pieces.iter().position(|p| match p {
+        Piece::Text(t) => t.chars().any(char::is_alphabetic),
+        Piece::Param(_, ParamType::Str, _) => true,
+        Piece::Param(_, _, _) | Piece::Ref(_) | Piece::TagOpen(_) | Piece::TagClose(_) => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    /// The function `titlecase` with its doc comment.
+    fn titlecase_source(source: &str) -> &str {
+        let start = source
+            .find("/// The titlecase of a char")
+            .expect("titlecase not found");
+        let end = start + source[start..].find("\n}\n").expect("end not found");
+        &source[start..end]
+    }
+
+    #[test]
+    fn same_titlecase() {
+        // the runtime crate is only available in the workspace
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../typed-i18n/src/case.rs");
+        let runtime = if let Ok(runtime) = std::fs::read_to_string(path) {
+            runtime
+        } else {
+            return;
+        };
+        assert_eq!(
+            titlecase_source(&runtime),
+            titlecase_source(include_str!("transform.rs"))
+        );
+    }
+}
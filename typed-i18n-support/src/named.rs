@@ -0,0 +1,132 @@
+use crate::diagnostic::Diagnostic;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{bracketed, parenthesized, Expr, Ident, Token, Type};
+
+/// A call with named arguments, created by the generated `named_macro`.
+///
+/// The input is `(language) function (generics) [declared parameters] name = value, ...`,
+/// or just `(language) function` for an unknown function.
+pub struct NamedCall {
+    language: Expr,
+    function: Ident,
+    generics: Punctuated<Type, Token![,]>,
+    params: Option<Vec<Ident>>,
+    args: Punctuated<NamedArg, Token![,]>,
+}
+
+struct NamedArg {
+    name: Ident,
+    value: Expr,
+}
+
+impl Parse for NamedArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(NamedArg { name, value })
+    }
+}
+
+impl Parse for NamedCall {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let language = content.parse()?;
+        let function = input.parse()?;
+        if input.is_empty() {
+            return Ok(NamedCall {
+                language,
+                function,
+                generics: Punctuated::new(),
+                params: None,
+                args: Punctuated::new(),
+            });
+        }
+        let content;
+        parenthesized!(content in input);
+        let generics = content.parse_terminated(Type::parse, Token![,])?;
+        let content;
+        bracketed!(content in input);
+        let mut params = Vec::new();
+        while !content.is_empty() {
+            params.push(content.parse()?);
+        }
+        let args = input.parse_terminated(NamedArg::parse, Token![,])?;
+        Ok(NamedCall {
+            language,
+            function,
+            generics,
+            params: Some(params),
+            args,
+        })
+    }
+}
+
+impl NamedCall {
+    #[must_use]
+    pub fn generate<D: Diagnostic>(self, diagnostic: &mut D) -> TokenStream {
+        let function = &self.function;
+        let params = if let Some(params) = self.params {
+            params
+        } else {
+            diagnostic.emit_error(function, format!("unknown message function {function}"));
+            diagnostic.should_abort_if_dirty();
+            return TokenStream::new();
+        };
+        let mut values = Vec::new();
+        let mut locals = Vec::new();
+        let mut ordered = vec![None; params.len()];
+        for arg in self.args {
+            let name = &arg.name;
+            if let Some(pos) = params.iter().position(|p| p == name) {
+                if ordered[pos].is_some() {
+                    diagnostic.emit_error(name, format!("duplicate parameter {name}"));
+                }
+                let local = Ident::new(&format!("__typed_i18n_{name}"), Span::mixed_site());
+                ordered[pos] = Some(local.clone());
+                locals.push(local);
+                values.push(arg.value);
+            } else {
+                diagnostic.emit_error(name, format!("unknown parameter {name} of {function}"));
+            }
+        }
+        let missing = params
+            .iter()
+            .zip(&ordered)
+            .filter(|(_, o)| o.is_none())
+            .map(|(p, _)| p.to_string())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            // the function identifier is from the macro rule, the language is from the call
+            let mut language = &self.language;
+            while let Expr::Group(g) = language {
+                language = &g.expr;
+            }
+            diagnostic.emit_error(
+                language,
+                format!("missing parameter {} of {function}", missing.join(", ")),
+            );
+        }
+        diagnostic.should_abort_if_dirty();
+
+        let language = &self.language;
+        let language_local = Ident::new("__typed_i18n_language", Span::mixed_site());
+        let ordered = ordered.into_iter().flatten();
+        let turbofish = if self.generics.is_empty() {
+            TokenStream::new()
+        } else {
+            let generics = &self.generics;
+            quote!(::<#generics>)
+        };
+        // a match is used so that all temporaries live until the end of the call
+        quote!(
+            match (#language, #(#values,)*) {
+                (#language_local, #(#locals,)*) => #language_local.#function #turbofish (#(#ordered),*),
+            }
+        )
+    }
+}
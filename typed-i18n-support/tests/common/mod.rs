@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 
 use proc_macro2::Span;
-use typed_i18n_support::attribute::Parameters;
+use typed_i18n_support::attribute::{Fallback, Parameters};
 use typed_i18n_support::languages::{Language, Languages, RawLanguages};
 
 pub struct Common;
@@ -16,7 +16,7 @@ impl Common {
             Language::run_new("en", &[]),
             Language::run_new("de", &[]),
         ])
-        .run_into()
+        .run_into(&Self::parameters_yaml())
         .expect("languages")
     }
 
@@ -31,4 +31,11 @@ impl Common {
     pub fn parameters_yaml() -> Parameters {
         Parameters::run_new("_.yaml", "_")
     }
+
+    pub fn parameters_hierarchy() -> Parameters {
+        Parameters {
+            fallback: Fallback::Hierarchy,
+            ..Parameters::run_new("_.yaml", "_")
+        }
+    }
 }
@@ -0,0 +1,83 @@
+use crate::common::Common;
+use syn::parse_quote;
+use typed_i18n_support::attribute::Attributes;
+use typed_i18n_support::diagnostic::Simulated;
+use typed_i18n_support::messages::Messages;
+
+mod common;
+
+#[test]
+fn documentation() {
+    let languages = Common::languages_en_de();
+    let messages = Messages::run_parse(
+        &Common::parameters_yaml(),
+        &languages,
+        "hello:\n  you:\n    en: Hello %{name}\n    de: Hallo `%{name}`\n  world:\n    _description: The greeting\n    _max_length: 20\n    en: Hello world\n",
+    )
+    .expect("found errors/warnings");
+    let mut diagnostic = Simulated::new();
+    let attributes = Attributes::parse(
+        &mut diagnostic,
+        Common::span(),
+        false,
+        vec![
+            parse_quote!(#[typed_i18n(filename = "_.yaml")]),
+            parse_quote!(#[typed_i18n(builder = "_")]),
+        ],
+    );
+    let generated = attributes
+        .generate(
+            &mut diagnostic,
+            &parse_quote!(pub),
+            &parse_quote!(Language),
+            "_.yaml",
+            &languages,
+            &messages,
+        )
+        .to_string();
+    diagnostic.assert(&[]);
+
+    for doc in [
+        "\" Message `hello_you`.\"",
+        "\" Path `hello.you`.\"",
+        "\" * `name`: str\"",
+        "\" * `en`: `Hello %{name}`\"",
+        "\" * `de`: `` Hallo `%{name}` ``\"",
+        "\" * `de` (fallback `en`): `Hello world`\"",
+        "\" The greeting\"",
+        "\" Maximum length: 20 chars.\"",
+    ] {
+        assert!(generated.contains(doc), "missing {doc} in {generated}");
+    }
+}
+
+#[test]
+fn compound_named_like_the_enum() {
+    let languages = Common::languages_en_de();
+    let messages = Messages::run_parse(
+        &Common::parameters_yaml(),
+        &languages,
+        "language:\n  _compound: true\n  name:\n    en: English\n    de: Deutsch\n",
+    )
+    .expect("found errors/warnings");
+    let mut diagnostic = Simulated::new();
+    let attributes = Attributes::parse(
+        &mut diagnostic,
+        Common::span(),
+        false,
+        vec![
+            parse_quote!(#[typed_i18n(filename = "_.yaml")]),
+            parse_quote!(#[typed_i18n(builder = "_")]),
+        ],
+    );
+    let _generated = attributes.generate(
+        &mut diagnostic,
+        &parse_quote!(pub),
+        &parse_quote!(Language),
+        "_.yaml",
+        &languages,
+        &messages,
+    );
+    diagnostic
+        .assert(&["Span: the struct name of compound language is the name of the enum Language"]);
+}
@@ -1,15 +1,17 @@
 mod common;
 
 use crate::common::Common;
+use typed_i18n_support::attribute::Parameters;
+use typed_i18n_support::charset::Charset;
 use typed_i18n_support::diagnostic::Simulated;
-use typed_i18n_support::languages::{Language, RawLanguages};
+use typed_i18n_support::languages::{Language, Languages, RawLanguages};
 
 // all this tests are independent of the format
 
 #[test]
 fn no_languages() {
     let diagnostic = &mut Simulated::new();
-    let _ = RawLanguages(vec![]).into(diagnostic, Common::span());
+    let _ = RawLanguages(vec![]).into(diagnostic, Common::span(), &Common::parameters_yaml());
     diagnostic.assert(&["Span: no languages found"]);
 }
 
@@ -20,21 +22,180 @@ fn duplicate_languages() {
         Language::run_new("en", &[]),
         Language::run_new("en", &[]),
     ])
-    .into(diagnostic, Common::span());
+    .into(diagnostic, Common::span(), &Common::parameters_yaml());
     diagnostic.assert(&["Span: language defined twice"]);
 }
 
+#[test]
+fn duplicate_source() {
+    let diagnostic = &mut Simulated::new();
+    let mut en = Language::run_new("en", &[]);
+    en.source = true;
+    let mut de = Language::run_new("de", &[]);
+    de.source = true;
+    let _ = RawLanguages(vec![en, de]).into(diagnostic, Common::span(), &Common::parameters_yaml());
+    diagnostic.assert(&["Span: found more than one source language"]);
+}
+
 #[test]
 fn fallback_itself() {
     let diagnostic = &mut Simulated::new();
-    let _ = RawLanguages(vec![Language::run_new("en", &["en"])]).into(diagnostic, Common::span());
+    let _ = RawLanguages(vec![Language::run_new("en", &["en"])]).into(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+    );
     diagnostic.assert(&["Span: fallback of itself"]);
 }
 
 #[test]
 fn unknown_fallback() {
     let diagnostic = &mut Simulated::new();
-    let _ =
-        RawLanguages(vec![Language::run_new("en", &["alien"])]).into(diagnostic, Common::span());
+    let _ = RawLanguages(vec![Language::run_new("en", &["alien"])]).into(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+    );
     diagnostic.assert(&["Span: unknown fallback: alien"]);
 }
+
+fn fallbacks(languages: &Languages) -> Vec<Vec<&str>> {
+    languages
+        .iter()
+        .map(|l| l.fallback.iter().map(String::as_str).collect())
+        .collect()
+}
+
+#[test]
+fn fallback_listing() {
+    let languages = RawLanguages(vec![
+        Language::run_new("de", &[]),
+        Language::run_new("en_au", &[]),
+        Language::run_new("en", &[]),
+    ])
+    .run_into(&Common::parameters_yaml())
+    .expect("languages");
+    assert_eq!(
+        fallbacks(&languages),
+        vec![
+            vec!["de", "en_au", "en"],
+            vec!["en_au", "de", "en"],
+            vec!["en", "de", "en_au"],
+        ]
+    );
+}
+
+#[test]
+fn fallback_hierarchy() {
+    let diagnostic = &mut Simulated::new();
+    let languages = RawLanguages(vec![
+        Language::run_new("de", &[]),
+        Language::run_new("en_au", &[]),
+        Language::run_new("zh_hant_tw", &["en"]),
+        Language::run_new("zh_hant", &[]),
+        Language::run_new("zh", &[]),
+        Language::run_new("en", &[]),
+    ])
+    .into(diagnostic, Common::span(), &Common::parameters_hierarchy());
+    assert_eq!(
+        fallbacks(&languages),
+        vec![
+            vec!["de", "en_au", "zh_hant_tw", "zh_hant", "zh", "en"],
+            vec!["en_au", "en", "de", "zh_hant_tw", "zh_hant", "zh"],
+            vec!["zh_hant_tw", "en", "zh_hant", "zh", "de", "en_au"],
+            vec!["zh_hant", "zh", "de", "en_au", "zh_hant_tw", "en"],
+            vec!["zh", "de", "en_au", "zh_hant_tw", "zh_hant", "en"],
+            vec!["en", "de", "en_au", "zh_hant_tw", "zh_hant", "zh"],
+        ]
+    );
+    diagnostic.assert(&[
+        "Span: warning: fallback of en_au is en_au, en, de, zh_hant_tw, zh_hant, zh; parent en before de, zh_hant_tw, zh_hant, zh",
+        "Span: warning: fallback of zh_hant_tw is zh_hant_tw, en, zh_hant, zh, de, en_au; parent zh_hant before de, en_au; parent zh before de, en_au",
+        "Span: warning: fallback of zh_hant is zh_hant, zh, de, en_au, zh_hant_tw, en; parent zh before de, en_au, zh_hant_tw",
+    ]);
+}
+
+#[test]
+fn fallback_hierarchy_missing_parent() {
+    let diagnostic = &mut Simulated::new();
+    let _ = RawLanguages(vec![
+        Language::run_new("zh_hant_tw", &[]),
+        Language::run_new("zh", &[]),
+        Language::run_new("en", &[]),
+    ])
+    .into(diagnostic, Common::span(), &Common::parameters_hierarchy());
+    diagnostic.assert(&[
+        "Span: warning: fallback of zh_hant_tw is zh_hant_tw, zh, en; unknown parent language: zh_hant",
+    ]);
+}
+
+#[test]
+fn languages_from_messages() {
+    let diagnostic = &mut Simulated::new();
+    let input = syn::parse_str::<syn::DeriveInput>(
+        r#"enum Language { #[typed_i18n(fallback = "de")] DeAt, English }"#,
+    )
+    .expect("enum");
+    let languages = RawLanguages::parse_with_messages(
+        diagnostic,
+        Common::span(),
+        input.data,
+        &Common::parameters_yaml(),
+        r#"
+hello:
+  en: Hello
+  de_at: Servus
+  zh-Hant: 你好
+  "1x": Hi
+bye:
+  de: Tschüss
+  en: Bye
+"#,
+    );
+    diagnostic.assert(&["Span: invalid language name 1x"]);
+    assert_eq!(
+        languages
+            .iter()
+            .map(|l| (l.ident.to_string(), l.name.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("DeAt".to_string(), "de_at"),
+            ("English".to_string(), "english"),
+            ("En".to_string(), "en"),
+            ("ZhHant".to_string(), "zh-Hant"),
+            ("De".to_string(), "de"),
+        ]
+    );
+}
+
+#[test]
+fn load_charsets() {
+    let diagnostic = &mut Simulated::new();
+    let mut en = Language::run_new("en", &[]);
+    en.charset_file = Some("en.txt".to_string());
+    let mut languages = RawLanguages(vec![
+        en,
+        Language::run_new("de", &[]),
+        Language::run_new("fr", &[]),
+    ])
+    .run_into(&Common::parameters_yaml())
+    .expect("languages");
+    let parameters = Parameters {
+        charset: Some(Charset::parse("U+0041-U+005A").unwrap()),
+        charset_file: Some("glyphs.txt".to_string()),
+        ..Common::parameters_yaml()
+    };
+    languages.load_charsets(diagnostic, Common::span(), &parameters, |file| match file {
+        "en.txt" => Ok("abc\n".to_string()),
+        _ => Err(format!("Error reading file {file}")),
+    });
+    diagnostic.assert(&["Span: Error reading file glyphs.txt"]);
+    let contains = |c| {
+        languages
+            .iter()
+            .map(|l| l.charset.as_ref().map_or(false, |cs| cs.contains(c)))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(contains('a'), vec![true, false, false]);
+    assert_eq!(contains('A'), vec![false, true, true]);
+}
@@ -75,3 +75,46 @@ en Hello
     );
     diagnostic.assert(&["Span: value without key at line 1"]);
 }
+
+#[test]
+fn declared_parameters() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_lrc(),
+        &Common::languages_en_de(),
+        r#"
+# hello
+_params first, last
+en Hello %{last}
+_params first
+"#,
+    );
+    diagnostic.assert(&["Span: duplicate metadata _params in key hello"]);
+}
+
+#[test]
+fn variants() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_lrc(),
+        &Common::languages_en_de(),
+        r#"
+# -brand
+en App
+en:genitive App's
+de:genitive der App
+de:genitive der App
+# hello
+en:genitive Hello
+"#,
+    );
+    diagnostic.assert(&[
+        "Span: language en key -brand has a text and variants",
+        "Span: duplicate language de:genitive at line 5",
+        "Span: language en:genitive key hello: only terms can have variants",
+    ]);
+}
@@ -1,8 +1,12 @@
 mod common;
 
 use crate::common::Common;
-use typed_i18n_support::attribute::Parameters;
+use std::collections::BTreeSet;
+use syn::parse_quote;
+use typed_i18n_support::attribute::{Attributes, Complete, Parameters, Placeholders};
+use typed_i18n_support::charset::Charset;
 use typed_i18n_support::diagnostic::Simulated;
+use typed_i18n_support::languages::{Language, RawLanguages};
 use typed_i18n_support::messages::Messages;
 
 // all this tests are independent of the format
@@ -85,6 +89,47 @@ fn duplicate_typed_parameter() {
     diagnostic.assert(&["Span: duplicate use of a typed parameter: \"name\" in key hello.en"]);
 }
 
+#[test]
+fn duplicate_typed_parameter_through_reference() {
+    let languages = Common::languages_en_de();
+    let messages = Messages::run_parse(
+        &Common::parameters_yaml(),
+        &languages,
+        "outer:\n  en: \"*{icon} @{inner}\"\ninner:\n  en: \"*{icon} x\"\n",
+    )
+    .expect("found errors/warnings");
+    for (input_conversion, errors) in [
+        (
+            "value",
+            &[
+                "Span: duplicate use of a typed parameter: \"icon\" in key outer.en (through a reference)",
+                "Span: duplicate use of a typed parameter: \"icon\" in key outer.de (through a reference)",
+            ][..],
+        ),
+        ("ref", &[][..]),
+    ] {
+        let diagnostic = &mut Simulated::new();
+        let attributes = Attributes::parse(
+            diagnostic,
+            Common::span(),
+            false,
+            vec![
+                parse_quote!(#[typed_i18n(filename = "_.yaml")]),
+                parse_quote!(#[typed_i18n(builder = "_", input = "_", input_conversion = #input_conversion)]),
+            ],
+        );
+        let _ = attributes.generate(
+            diagnostic,
+            &parse_quote!(pub),
+            &parse_quote!(Language),
+            "_.yaml",
+            &languages,
+            &messages,
+        );
+        diagnostic.assert(errors);
+    }
+}
+
 #[test]
 fn key_without_values() {
     let diagnostic = &mut Simulated::new();
@@ -123,3 +168,682 @@ fn parse_error2() {
     );
     diagnostic.assert(&["Span: parse error in hello.en"]);
 }
+
+#[test]
+fn undeclared_parameter() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        r#"{"hello": {"_params": ["name"], "en": "Hello %{name}", "de": "Hallo %{first}"} }"#,
+    );
+    diagnostic.assert(&["Span: undeclared parameter first in hello.de"]);
+}
+
+#[test]
+fn unused_declared_parameter() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        r#"{"hello": {"_params": ["first", "last"], "en": "Hello %{first}"} }"#,
+    );
+    diagnostic.assert(&["Span: declared parameter last is not used in key hello"]);
+}
+
+#[test]
+fn invalid_declared_parameters() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        r#"{"hello": {"_params": "name name 1st", "en": "Hello %{name}"} }"#,
+    );
+    diagnostic.assert(&[
+        "Span: duplicate declared parameter name in key hello",
+        "Span: invalid declared parameter name \"1st\" in key hello",
+    ]);
+}
+
+#[test]
+fn unknown_metadata() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        r#"{"hello": {"_unknown": "x", "en": "Hello"} }"#,
+    );
+    diagnostic.assert(&["Span: unknown metadata _unknown in key hello"]);
+}
+
+#[test]
+fn metadata_errors() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+save:
+  _description: [The, button]
+  _note: 1
+  _tags: 2
+  _max_length: -1
+  en: Save
+open:
+  _max_length: long
+  _note: [a, b]
+  _deprecated: [old]
+  en: Open
+"#,
+    );
+    diagnostic.assert(&[
+        "Span: metadata _description in key save is not a string",
+        "Span: metadata _note in key save is not a string",
+        "Span: metadata _tags in key save is not a list",
+        "Span: metadata _max_length in key save is not a valid length",
+        "Span: metadata _max_length in key open is not a valid length",
+        "Span: metadata _note in key open is not a string",
+        "Span: metadata _deprecated in key open is not a string",
+    ]);
+}
+
+#[test]
+fn max_length_errors() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Parameters {
+            param_length: 4,
+            ..Common::parameters_yaml()
+        },
+        &Common::languages_en_de(),
+        r#"
+button:
+  _max_length: 6
+  save:
+    en: Save
+    de: Speichern
+  greet:
+    en: Hi %{name}
+    de: Hallo %{name}
+  street:
+    _transform: upper
+    _max_length: 6 chars
+    en: Road
+    de: Straße
+  days:
+    en: [Mo, Tu]
+    de: [Mo, Die]
+    _max_length: 2
+lcd:
+  _max_length: 4 bytes
+  en: Grüße
+  de: Gruß
+title:
+  _max_length: 4
+  en: Settings
+wide:
+  _max_length: 4 columns
+  en: Save
+  de: 保存する
+"#,
+    );
+    diagnostic.assert(&[
+        "Span: button_save.de is 9 chars long, the maximum is 6",
+        "Span: button_greet.en is 7 chars long, the maximum is 6",
+        "Span: button_greet.de is 10 chars long, the maximum is 6",
+        "Span: button_street.de is 7 chars long, the maximum is 6",
+        "Span: button_days.de[1] is 3 chars long, the maximum is 2",
+        "Span: lcd.en is 7 bytes long, the maximum is 4",
+        "Span: lcd.de is 5 bytes long, the maximum is 4",
+        "Span: title.en is 8 chars long, the maximum is 4",
+        "Span: title.de (fallback en) is 8 chars long, the maximum is 4",
+        "Span: wide.de is 8 columns long, the maximum is 4",
+    ]);
+}
+
+#[test]
+fn max_length_with_invalid_references() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+a:
+  _max_length: 5
+  en: "@{b}"
+b:
+  en: "@{a}"
+"#,
+    );
+    diagnostic.assert(&["Span: reference cycle: a -> b -> a"]);
+
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+a:
+  _max_length: 5
+  en: "@{zzz}"
+"#,
+    );
+    diagnostic.assert(&["Span: unknown reference zzz in a.en"]);
+}
+
+#[test]
+fn alias_errors() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+hello:
+  _aliases: [hi, bye, 1st]
+  en: Hello
+bye:
+  _aliases: hi
+  _deprecated: false
+  en: Bye
+-brand:
+  _aliases: brand
+  en: App
+"#,
+    );
+    diagnostic.assert(&[
+        "Span: alias bye of key hello is also a key",
+        "Span: invalid alias \"1st\" in key hello",
+        "Span: duplicate alias hi in key bye",
+        "Span: key -brand can't have aliases",
+    ]);
+}
+
+#[test]
+fn transform_errors() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        r#"{
+            "hello": {"_transform": "shout", "en": "Hello"},
+            "bye": {"_transform": ["upper"], "en": "Bye"}
+        }"#,
+    );
+    diagnostic.assert(&[
+        "Span: unknown transform shout in key hello",
+        "Span: metadata _transform in key bye is not a string",
+    ]);
+}
+
+#[test]
+fn parameter_transform_errors() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        r#"{"hello": {"en": "Hello %{name:loud}", "de": "Hallo *{name:upper}"} }"#,
+    );
+    diagnostic.assert(&[
+        "Span: parse error in hello.en",
+        "Span: only str parameters can be transformed: name in hello.de",
+    ]);
+}
+
+#[test]
+fn parse_error_icu() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Parameters {
+            placeholders: Placeholders::Icu,
+            ..Common::parameters_json()
+        },
+        &Common::languages_en_de(),
+        r#"{"hello": {"en": "Hello {name", "de": "Hallo {name, number}"} }"#,
+    );
+    diagnostic.assert(&[
+        "Span: parse error in hello.en",
+        "Span: invalid parameter name \"name, number\" in hello.de",
+    ]);
+}
+
+#[test]
+fn parse_error_i18next() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Parameters {
+            placeholders: Placeholders::I18next,
+            ..Common::parameters_json()
+        },
+        &Common::languages_en_de(),
+        r#"{"hello": {"en": "Hello {{name}", "de": "Hallo {{}}"} }"#,
+    );
+    diagnostic.assert(&[
+        "Span: parse error in hello.en",
+        "Span: invalid parameter name \"\" in hello.de",
+    ]);
+}
+
+#[test]
+fn unknown_reference() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        r#"{"hello": {"en": "Hello @{name}"} }"#,
+    );
+    diagnostic.assert(&["Span: unknown reference name in hello.en"]);
+}
+
+#[test]
+fn reference_cycle() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        r#"{"a": {"en": "@{b}"}, "b": {"en": "x", "de": "@{c}"}, "c": {"en": "@{a}"} }"#,
+    );
+    diagnostic.assert(&["Span: reference cycle: a -> b -> c -> a"]);
+}
+
+#[test]
+fn reference_parameters() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        r#"{
+            "name": {"en": "%{first} %{last}"},
+            "hello": {"_params": ["first"], "en": "Hello @{name}"},
+            "bye": {"en": "Bye *{first} @{name}"}
+        }"#,
+    );
+    diagnostic.assert(&[
+        "Span: undeclared parameter last in hello.en (referenced name)",
+        "Span: mismatching types for parameter first in key bye.en (referenced name)",
+    ]);
+}
+
+#[test]
+fn missing_variant() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+-brand:
+  en: App
+  de:
+    nominative: die App
+hello:
+  en: Hello @{brand}
+  de: Hallo @{brand:dative}
+"#,
+    );
+    diagnostic
+        .assert(&["Span: variant dative of term -brand is missing in de (referenced in hello.de)"]);
+}
+
+#[test]
+fn list_errors() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+weekdays:
+  en: [Mon, Tue]
+  de: [Mo, Di, Mi]
+hello:
+  en: Hello @{weekdays}
+"#,
+    );
+    diagnostic.assert(&[
+        "Span: list weekdays.de has 3 items, but weekdays.en has 2",
+        "Span: reference to the list weekdays in hello.en",
+    ]);
+}
+
+#[test]
+fn constant_errors() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+first_day:
+  _type: u8
+  en: 256
+  de: Monday
+sunday_first:
+  _type: bool
+  en: yes
+  fr: true
+order:
+  _type: DateOrder
+  _transform: upper
+  en: Day Month
+  de: [Day, Month]
+width:
+  _type: "[u8"
+  en: 1
+hello:
+  en: 4
+  de: Hallo @{columns}
+columns:
+  _type: u8
+  en: 2
+-count:
+  _type: u8
+  en: 1
+2nd_day:
+  _type: u8
+  en: 2
+max_id:
+  _type: u128
+  en: "18446744073709551616"
+min_id:
+  _type: u64
+  en: "18446744073709551616"
+name:
+  _type: String
+  en: English
+ratio:
+  _type: f32
+  en: 1
+label:
+  _type: "&str"
+  en: en
+items:
+  _type: Vec<u8>
+  en: 1
+"#,
+    );
+    diagnostic.assert(&[
+        "Span: invalid value Monday of type u8 in first_day.de",
+        "Span: invalid value 256 of type u8 in first_day.en",
+        "Span: invalid value yes of type bool in sunday_first.en",
+        "Span: language fr key sunday_first is not known",
+        "Span: constant order can't have lists or variants",
+        "Span: constant order can't have _params or _transform",
+        "Span: invalid value Day Month of type DateOrder in order.en",
+        "Span: invalid type [u8 in key width",
+        "Span: language en key hello is not a string",
+        "Span: term -count can't have a _type",
+        r#"Span: invalid constant name "2nd_day""#,
+        "Span: invalid value 18446744073709551616 of type u64 in min_id.en",
+        "Span: unsupported type String in key name, expected an integer, bool or enum",
+        "Span: unsupported type f32 in key ratio, expected an integer, bool or enum",
+        "Span: unsupported type &str in key label, expected an integer, bool or enum",
+        "Span: unsupported type Vec<u8> in key items, expected an integer, bool or enum",
+        "Span: reference to the constant columns in hello.de",
+    ]);
+}
+
+#[test]
+fn variant_of_message() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+brand:
+  en: App
+hello:
+  en: Hello @{brand:dative}
+"#,
+    );
+    diagnostic.assert(&["Span: only terms have variants, brand:dative in hello.en"]);
+}
+
+#[test]
+fn markup_errors() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Parameters {
+            markup: true,
+            ..Common::parameters_json()
+        },
+        &Common::languages_en_de(),
+        r#"{
+            "hello": {"en": "<b>Hello <i>you</b></i>", "de": "<b>Hallo"},
+            "bye": {"en": "<b>Bye</b> <<b>", "de": "<i>Tschüss</i>"}
+        }"#,
+    );
+    diagnostic.assert(&[
+        "Span: unbalanced tag b in hello.en",
+        "Span: unclosed tag b in hello.de",
+        "Span: the tags of hello.de differ from hello.en",
+        "Span: the tags of bye.de differ from bye.en",
+    ]);
+}
+
+#[test]
+fn charset_errors() {
+    let diagnostic = &mut Simulated::new();
+    let parameters = Parameters {
+        charset: Some(Charset::parse("ascii").unwrap()),
+        ..Common::parameters_yaml()
+    };
+    let mut languages = Common::languages_en_de();
+    languages.load_charsets(diagnostic, Common::span(), &parameters, |_| {
+        unreachable!("no files")
+    });
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &parameters,
+        &languages,
+        r#"
+-brand:
+  en: Café
+  de:
+    nominative: Café
+    dative: Cafés
+hello:
+  en: Hello @{brand} ✓
+  de: Grüße ✓
+street:
+  _transform: upper
+  en: Road
+  de: Straße
+days:
+  en: [Mo, Tu]
+  de: [Mo, Dü]
+visit:
+  en: Visit @{brand:dative}
+months:
+  en: [Jän, Feb]
+"#,
+    );
+    diagnostic.assert(&[
+        "Span: unsupported chars in -brand.en: 'é' (U+00E9)",
+        "Span: unsupported chars in -brand.de:nominative: 'é' (U+00E9)",
+        "Span: unsupported chars in -brand.de:dative: 'é' (U+00E9)",
+        "Span: unsupported chars in hello.en: 'é' (U+00E9), '✓' (U+2713)",
+        "Span: unsupported chars in hello.de: 'ß' (U+00DF), 'ü' (U+00FC), '✓' (U+2713)",
+        "Span: unsupported chars in days.de[1]: 'ü' (U+00FC)",
+        "Span: unsupported chars in visit.en: 'é' (U+00E9)",
+        "Span: unsupported chars in visit.de (fallback en): 'é' (U+00E9)",
+        "Span: unsupported chars in months.en[0]: 'ä' (U+00E4)",
+        "Span: unsupported chars in months.de[0] (fallback en): 'ä' (U+00E4)",
+    ]);
+}
+
+#[test]
+fn charsets_with_invalid_references() {
+    let parameters = Parameters {
+        charset: Some(Charset::parse("ascii").unwrap()),
+        ..Common::parameters_yaml()
+    };
+    for (content, error) in [
+        (
+            "a:\n  en: \"@{b} ä\"\nb:\n  en: \"@{a}\"\n",
+            "Span: reference cycle: a -> b -> a",
+        ),
+        (
+            "a:\n  en: \"@{zzz} ä\"\n",
+            "Span: unknown reference zzz in a.en",
+        ),
+    ] {
+        let diagnostic = &mut Simulated::new();
+        let mut languages = Common::languages_en_de();
+        languages.load_charsets(diagnostic, Common::span(), &parameters, |_| {
+            unreachable!("no files")
+        });
+        let messages =
+            Messages::parse(diagnostic, Common::span(), &parameters, &languages, content);
+        diagnostic.assert(&[error]);
+        assert_eq!(
+            messages.used_chars(&languages),
+            vec![
+                ("en".to_string(), BTreeSet::new()),
+                ("de".to_string(), BTreeSet::new())
+            ]
+        );
+    }
+}
+
+#[test]
+fn used_chars() {
+    let diagnostic = &mut Simulated::new();
+    let messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+-brand:
+  en: Cafe
+hello:
+  en: Hi @{brand} %{name}
+  de: Moin
+street:
+  _transform: upper
+  en: road
+  de: straße
+days:
+  en: [Mo, Tu]
+"#,
+    );
+    diagnostic.assert(&[]);
+    let used = messages
+        .used_chars(&Common::languages_en_de())
+        .into_iter()
+        .map(|(l, chars)| (l, chars.into_iter().collect::<String>()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        used,
+        vec![
+            ("en".to_string(), " ACDHMORTaefiou".to_string()),
+            ("de".to_string(), "AEMRSTinou".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn completeness() {
+    let languages = |source: bool, parameters: &Parameters| {
+        let mut en = Language::run_new("en", &[]);
+        en.source = source;
+        let mut fr = Language::run_new("fr", &[]);
+        fr.complete = Some(Complete::Warn);
+        let mut it = Language::run_new("it", &[]);
+        it.complete = Some(Complete::No);
+        RawLanguages(vec![en, Language::run_new("de", &[]), fr, it])
+            .run_into(parameters)
+            .expect("languages")
+    };
+    let content = r#"
+-brand:
+  en: App
+  de: App
+  fr:
+    nominative: l'App
+hello:
+  en: Hello
+  de: Hallo
+  fr: Bonjour
+maybe_note:
+  en: ""
+  fr: ""
+days:
+  en: [Mo, Tu]
+  de: [Mo, Di]
+  fr: [Lu, ""]
+new:
+  de: Neu
+"#;
+
+    let parameters = Parameters {
+        require_complete: Complete::Yes,
+        ..Common::parameters_yaml()
+    };
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &parameters,
+        &languages(false, &parameters),
+        content,
+    );
+    diagnostic.assert(&[
+        "Span: language en is missing: new",
+        "Span: language de is missing: maybe_note",
+        "Span: warning: language fr is missing: new",
+    ]);
+
+    let parameters = Parameters {
+        empty_is_missing: true,
+        ..parameters
+    };
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &parameters,
+        &languages(true, &parameters),
+        content,
+    );
+    diagnostic.assert(&["Span: warning: language fr is missing: days"]);
+}
@@ -1,5 +1,5 @@
 use crate::common::Common;
-use typed_i18n_support::messages::Messages;
+use typed_i18n_support::messages::{LengthUnit, MaxLength, Messages};
 
 mod common;
 
@@ -16,3 +16,211 @@ fn serde_output() {
     // the tree is still flat as the source is flat!
     assert_eq!(flat, tree);
 }
+
+#[test]
+fn serde_output_declared_params() {
+    let input = r#"{"hello":{"you":{"_params":["first","last"],"en":"Hello %{last}, %{first}"}}}"#;
+    let messages = Messages::run_parse(
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        input,
+    )
+    .expect("found errors/warnings");
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(tree, format!(r#"{{"_version":2,{}"#, &input[1..]));
+    assert_eq!(
+        messages.to_lrc(),
+        "#hello_you\n_params first last\nen Hello %{last}, %{first}\n\n"
+    );
+}
+
+#[test]
+fn serde_output_transform() {
+    let input = r#"{"hello":{"_transform":"capitalize","en":"hello %{name:upper}"}}"#;
+    let messages = Messages::run_parse(
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        input,
+    )
+    .expect("found errors/warnings");
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(tree, format!(r#"{{"_version":2,{}"#, &input[1..]));
+    assert_eq!(
+        messages.to_lrc(),
+        "#hello\n_transform capitalize\nen hello %{name:upper}\n\n"
+    );
+}
+
+#[test]
+fn serde_output_lists() {
+    let input =
+        r#"{"steps":{"en":["Sign up","Say %{greeting}"],"de":["Anmelden","Sag %{greeting}"]}}"#;
+    let messages = Messages::run_parse(
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        input,
+    )
+    .expect("found errors/warnings");
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(tree, format!(r#"{{"_version":2,{}"#, &input[1..]));
+    let lrc = messages.to_lrc();
+    assert_eq!(
+        lrc,
+        "#steps\nen[] Sign up\nen[] Say %{greeting}\nde[] Anmelden\nde[] Sag %{greeting}\n\n"
+    );
+    let messages = Messages::run_parse(&Common::parameters_lrc(), &Common::languages_en_de(), &lrc)
+        .expect("found errors/warnings");
+    assert_eq!(messages.to_lrc(), lrc);
+}
+
+#[test]
+fn serde_output_constants() {
+    let input = r#"{"first_day":{"_type":"i8","en":-1,"de":1},"sunday_first":{"_type":"bool","en":true},"order":{"_type":"DateOrder","en":"MonthDay"}}"#;
+    let messages = Messages::run_parse(
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        input,
+    )
+    .expect("found errors/warnings");
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(tree, format!(r#"{{"_version":2,{}"#, &input[1..]));
+    let lrc = messages.to_lrc();
+    assert_eq!(
+        lrc,
+        "#first_day\n_type i8\nen -1\nde 1\n\n#sunday_first\n_type bool\nen true\n\n#order\n_type DateOrder\nen MonthDay\n\n"
+    );
+    let messages = Messages::run_parse(&Common::parameters_lrc(), &Common::languages_en_de(), &lrc)
+        .expect("found errors/warnings");
+    assert_eq!(messages.to_lrc(), lrc);
+}
+
+#[test]
+fn serde_output_metadata() {
+    let input = r#"{"save":{"_description":"The button to save the document","_note":"Imperative","_tags":["ui","button"],"_max_length":12,"en":"Save","de":"Speichern"},"lcd":{"_max_length":"8 bytes","en":"Ready"}}"#;
+    let messages = Messages::run_parse(
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        input,
+    )
+    .expect("found errors/warnings");
+    let metadata = messages.iter().next().expect("a message").1.metadata();
+    assert_eq!(
+        metadata.description(),
+        Some("The button to save the document")
+    );
+    assert_eq!(metadata.note(), Some("Imperative"));
+    assert_eq!(
+        metadata
+            .tags()
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>(),
+        ["ui", "button"]
+    );
+    assert_eq!(
+        metadata.max_length(),
+        Some(MaxLength {
+            length: 12,
+            unit: LengthUnit::Chars
+        })
+    );
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(tree, format!(r#"{{"_version":2,{}"#, &input[1..]));
+    let lrc = messages.to_lrc();
+    assert_eq!(
+        lrc,
+        "#save\n_description The button to save the document\n_note Imperative\n_tags ui button\n_max_length 12\nen Save\nde Speichern\n\n#lcd\n_max_length 8 bytes\nen Ready\n\n"
+    );
+    let messages = Messages::run_parse(&Common::parameters_lrc(), &Common::languages_en_de(), &lrc)
+        .expect("found errors/warnings");
+    assert_eq!(messages.to_lrc(), lrc);
+}
+
+#[test]
+fn serde_output_aliases() {
+    let input = r#"{"greeting":{"_aliases":["hello","hi"],"en":"Hello"},"bye":{"_deprecated":true,"en":"Bye"},"ciao":{"_deprecated":"use bye","en":"Ciao"}}"#;
+    let messages = Messages::run_parse(
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        input,
+    )
+    .expect("found errors/warnings");
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(tree, format!(r#"{{"_version":2,{}"#, &input[1..]));
+    let lrc = messages.to_lrc();
+    assert_eq!(
+        lrc,
+        "#greeting\n_aliases hello hi\nen Hello\n\n#bye\n_deprecated\nen Bye\n\n#ciao\n_deprecated use bye\nen Ciao\n\n"
+    );
+    let messages = Messages::run_parse(&Common::parameters_lrc(), &Common::languages_en_de(), &lrc)
+        .expect("found errors/warnings");
+    assert_eq!(messages.to_lrc(), lrc);
+}
+
+#[test]
+fn serde_output_deprecated_bool() {
+    let messages = Messages::run_parse(
+        &Common::parameters_lrc(),
+        &Common::languages_en_de(),
+        "#bye\n_deprecated true\nen Bye\n\n#ciao\n_deprecated false\nen Ciao\n\n",
+    )
+    .expect("found errors/warnings");
+    let lrc = messages.to_lrc();
+    assert_eq!(lrc, "#bye\n_deprecated\nen Bye\n\n#ciao\nen Ciao\n\n");
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(
+        tree,
+        r#"{"_version":2,"bye":{"_deprecated":true,"en":"Bye"},"ciao":{"en":"Ciao"}}"#
+    );
+    let messages = Messages::run_parse(&Common::parameters_lrc(), &Common::languages_en_de(), &lrc)
+        .expect("found errors/warnings");
+    assert_eq!(messages.to_lrc(), lrc);
+}
+
+#[test]
+fn serde_output_compound() {
+    let input =
+        r#"{"email":{"_compound":true,"subject":{"en":"Hello %{name}"},"body":{"en":"Bye"}}}"#;
+    let messages = Messages::run_parse(
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        input,
+    )
+    .expect("found errors/warnings");
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(tree, format!(r#"{{"_version":2,{}"#, &input[1..]));
+}
+
+#[test]
+fn serde_output_escapes() {
+    let input = r#"{"hello":{"en":"%%{name} **{icon} %{name}}"}}"#;
+    let messages = Messages::run_parse(
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        input,
+    )
+    .expect("found errors/warnings");
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(tree, format!(r#"{{"_version":2,{}"#, &input[1..]));
+    assert_eq!(
+        messages.to_lrc(),
+        "#hello\nen %%{name} **{icon} %{name}}\n\n"
+    );
+}
+
+#[test]
+fn serde_output_variants() {
+    let input = r#"{"-brand":{"en":"App","de":{"nominative":"die App","genitive":"der App"}},"hello":{"de":"Hallo @{brand:nominative}"}}"#;
+    let messages = Messages::run_parse(
+        &Common::parameters_json(),
+        &Common::languages_en_de(),
+        input,
+    )
+    .expect("found errors/warnings");
+    let tree = serde_json::to_string(&messages.as_tree()).expect("found serde_json errors");
+    assert_eq!(tree, format!(r#"{{"_version":2,{}"#, &input[1..]));
+    assert_eq!(
+        messages.to_lrc(),
+        "#-brand\nen App\nde:nominative die App\nde:genitive der App\n\n#hello\nde Hallo @{brand:nominative}\n\n"
+    );
+}
@@ -25,9 +25,9 @@ fn invalid_json() {
         Common::span(),
         &Common::parameters_json(),
         &Common::languages_en_de(),
-        r#"{"hello": {"en": 4} }"#,
+        r#"{"hello": {"en": null} }"#,
     );
-    diagnostic.assert(&["Span: Invalid JSON format, data did not match any variant of untagged enum ObjectOrString at line 1 column 21"]);
+    diagnostic.assert(&["Span: Invalid JSON format, data did not match any variant of untagged enum ObjectOrString at line 1 column 24"]);
 }
 
 #[test]
@@ -40,8 +40,104 @@ fn invalid_yaml() {
         &Common::languages_en_de(),
         r#"
 hello:
-  en: 4
+  en: ~
 "#,
     );
     diagnostic.assert(&["Span: Invalid YAML format, data did not match any variant of untagged enum ObjectOrString at line 2 column 1"]);
 }
+
+#[test]
+fn mixed_sub_keys() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+hello:
+  en: Hello
+  world:
+    en: Hello world
+"#,
+    );
+    diagnostic.assert(&["Span: key hello contains both sub keys and the entry en"]);
+}
+
+#[test]
+fn list_and_text() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+hello:
+  en: [Hello]
+  de: Hallo
+"#,
+    );
+    diagnostic.assert(&["Span: key hello has texts and lists"]);
+}
+
+#[test]
+fn compound_errors() {
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+email:
+  _compound: true
+  subject:
+    en: Hello
+  footer:
+    legal:
+      en: Legal
+"#,
+    );
+    diagnostic.assert(&["Span: compound email contains the sub keys email_footer"]);
+
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+email:
+  _compound: true
+  subject:
+    en: Hello %{name}
+  body:
+    en: ["Hello *{name}"]
+"#,
+    );
+    diagnostic.assert(&[
+        "Span: compound email contains the list email_body",
+        "Span: mismatching types for parameter name in compound email",
+    ]);
+
+    let diagnostic = &mut Simulated::new();
+    let _messages = Messages::parse(
+        diagnostic,
+        Common::span(),
+        &Common::parameters_yaml(),
+        &Common::languages_en_de(),
+        r#"
+user_email:
+  _compound: true
+  subject:
+    en: Hello
+userEmail:
+  _compound: true
+  subject:
+    en: Hello
+"#,
+    );
+    diagnostic
+        .assert(&["Span: compounds user_email and userEmail have the same struct name UserEmail"]);
+}
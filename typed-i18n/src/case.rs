@@ -0,0 +1,165 @@
+use core::iter::Peekable;
+use core::str::Chars;
+
+/// A case transform of a parameter or a message, see [case transforms](crate#case-transforms).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// All letters uppercase.
+    Upper,
+    /// All letters lowercase.
+    Lower,
+    /// The first letter uppercase (titlecase), all others are unchanged.
+    Capitalize,
+}
+
+/// Map the case of a text, without allocation.
+///
+/// With `turkic` the dotted and dotless i are mapped as in Turkish and Azerbaijani
+/// (`i` ↔ `İ`, `ı` ↔ `I`).
+///
+/// ```rust
+/// # use typed_i18n::{map_case, Case};
+/// assert_eq!(map_case("straße", Case::Upper, false).collect::<String>(), "STRASSE");
+/// assert_eq!(map_case("istanbul", Case::Capitalize, true).collect::<String>(), "İstanbul");
+/// assert_eq!(map_case("ΟΔΟΣ", Case::Lower, false).collect::<String>(), "οδος");
+/// ```
+#[must_use]
+pub fn map_case(text: &str, case: Case, turkic: bool) -> CaseMapping<'_> {
+    CaseMapping {
+        chars: text.chars().peekable(),
+        case,
+        turkic,
+        previous_letter: false,
+        capitalized: false,
+        pending: ['\0'; 3],
+        pending_start: 0,
+        pending_end: 0,
+    }
+}
+
+/// Iterator over the chars of a text with a mapped case, see [`map_case`].
+#[derive(Clone, Debug)]
+pub struct CaseMapping<'a> {
+    chars: Peekable<Chars<'a>>,
+    case: Case,
+    turkic: bool,
+    previous_letter: bool,
+    capitalized: bool,
+    pending: [char; 3],
+    pending_start: usize,
+    pending_end: usize,
+}
+
+impl CaseMapping<'_> {
+    fn set_pending(&mut self, chars: impl Iterator<Item = char>) {
+        self.pending_start = 0;
+        self.pending_end = 0;
+        for c in chars {
+            self.pending[self.pending_end] = c;
+            self.pending_end += 1;
+        }
+    }
+
+    fn upper(&mut self, c: char) {
+        if self.turkic && c == 'i' {
+            self.set_pending(core::iter::once('İ'));
+        } else {
+            self.set_pending(c.to_uppercase());
+        }
+    }
+
+    fn lower(&mut self, c: char) {
+        if self.turkic && c == 'I' {
+            self.set_pending(core::iter::once('ı'));
+        } else if self.turkic && c == 'İ' {
+            self.set_pending(core::iter::once('i'));
+        } else if c == 'Σ' {
+            // final sigma: after a letter and not before one
+            let is_final =
+                self.previous_letter && !self.chars.peek().map_or(false, |n| n.is_alphabetic());
+            self.set_pending(core::iter::once(if is_final { 'ς' } else { 'σ' }));
+        } else {
+            self.set_pending(c.to_lowercase());
+        }
+    }
+
+    fn capitalize(&mut self, c: char) {
+        if self.capitalized || !c.is_alphabetic() {
+            self.set_pending(core::iter::once(c));
+        } else {
+            self.capitalized = true;
+            if let Some((first, rest)) = titlecase(c) {
+                self.set_pending(core::iter::once(first).chain(rest.chars()));
+            } else {
+                self.upper(c);
+            }
+        }
+    }
+}
+
+// the same as in typed-i18n-support/src/messages/transform.rs (for the compile time transform)
+
+/// The titlecase of a char if it differs from the uppercase (as the first char and the rest),
+/// e.g. of digraphs, ligatures, Georgian and Greek with ypogegrammeni.
+fn titlecase(c: char) -> Option<(char, &'static str)> {
+    Some(match c {
+        'ß' => ('S', "s"),
+        'Ǆ' | 'ǅ' | 'ǆ' => ('ǅ', ""),
+        'Ǉ' | 'ǈ' | 'ǉ' => ('ǈ', ""),
+        'Ǌ' | 'ǋ' | 'ǌ' => ('ǋ', ""),
+        'Ǳ' | 'ǲ' | 'ǳ' => ('ǲ', ""),
+        'և' => ('Ե', "ւ"),
+        'ﬀ' => ('F', "f"),
+        'ﬁ' => ('F', "i"),
+        'ﬂ' => ('F', "l"),
+        'ﬃ' => ('F', "fi"),
+        'ﬄ' => ('F', "fl"),
+        'ﬅ' | 'ﬆ' => ('S', "t"),
+        'ﬓ' => ('Մ', "ն"),
+        'ﬔ' => ('Մ', "ե"),
+        'ﬕ' => ('Մ', "ի"),
+        'ﬖ' => ('Վ', "ն"),
+        'ﬗ' => ('Մ', "խ"),
+        '\u{1f80}'..='\u{1f87}' | '\u{1f90}'..='\u{1f97}' | '\u{1fa0}'..='\u{1fa7}' => {
+            (char::from_u32(u32::from(c) + 8)?, "")
+        }
+        // Georgian Mkhedruli and Greek with prosgegrammeni are their own titlecase
+        '\u{10d0}'..='\u{10fa}'
+        | '\u{10fd}'..='\u{10ff}'
+        | '\u{1f88}'..='\u{1f8f}'
+        | '\u{1f98}'..='\u{1f9f}'
+        | '\u{1fa8}'..='\u{1faf}' => (c, ""),
+        'ᾳ' | 'ᾼ' => ('ᾼ', ""),
+        'ῃ' | 'ῌ' => ('ῌ', ""),
+        'ῳ' | 'ῼ' => ('ῼ', ""),
+        'ᾲ' => ('\u{1fba}', "\u{345}"),
+        'ᾴ' => ('\u{386}', "\u{345}"),
+        'ᾷ' => ('\u{391}', "\u{342}\u{345}"),
+        'ῂ' => ('\u{1fca}', "\u{345}"),
+        'ῄ' => ('\u{389}', "\u{345}"),
+        'ῇ' => ('\u{397}', "\u{342}\u{345}"),
+        'ῲ' => ('\u{1ffa}', "\u{345}"),
+        'ῴ' => ('\u{38f}', "\u{345}"),
+        'ῷ' => ('\u{3a9}', "\u{342}\u{345}"),
+        _ => return None,
+    })
+}
+
+impl Iterator for CaseMapping<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.pending_start == self.pending_end {
+            let c = self.chars.next()?;
+            match self.case {
+                Case::Upper => self.upper(c),
+                Case::Lower => self.lower(c),
+                Case::Capitalize => self.capitalize(c),
+            }
+            self.previous_letter = c.is_alphabetic();
+        }
+        let c = self.pending[self.pending_start];
+        self.pending_start += 1;
+        Some(c)
+    }
+}
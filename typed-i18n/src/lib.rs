@@ -136,6 +136,17 @@
 //! - `filename`: the path to the translations, relative to the crate root (required).
 //! - `separator`: used for combining paths of a tree, default: `_`.
 //! - `global`: used for a global stored language, see [global](#global) below, default: not used.
+//! - `fallback`: how the fallback of the languages is completed, see [language](#language) below, default: `listing`.
+//! - `named_macro`: name of a macro for calls with named arguments, see [named arguments](#named-arguments) below, default: not generated.
+//! - `placeholders`: syntax of the parameters in the messages, see [placeholders](#placeholders) below, default: `percent`.
+//! - `markup`: parse tags in the messages, see [markup](#markup) below, default: `false`.
+//! - `negotiate`: generate the function `negotiate`, see [negotiation](#negotiation) below, default: `false`.
+//! - `direction`: generate the function `direction`, see [direction](#direction) below, default: `false`.
+//! - `param_length`: the estimated length of a parameter, see [maximum length](#maximum-length) below, default: `0`.
+//! - `charset`: the chars all languages can display, see [character set](#character-set) below, default: not checked.
+//! - `charset_file`: a file with the glyphs all languages can display, relative to the crate root, see [character set](#character-set) below, default: not checked.
+//! - `require_complete`: whether all languages must have all keys, see [completeness](#completeness) below, default: `false`.
+//! - `empty_is_missing`: an empty text counts as a missing key, see [completeness](#completeness) below, default: `false`.
 //!
 //! Example:
 //!
@@ -177,15 +188,331 @@
 //!   en Hello World
 //! ```
 //!
+//! ## Parameter order
+//!
+//! The parameters of a generated function are in the order of their first use (all languages in file order).
+//! To get a stable signature the parameters can be declared with a `_params` entry:
+//!
+//! ```yaml
+//! hello_full:
+//!   _params: [first, last]
+//!   en: Hello %{first} %{last}
+//!   ja: こんにちは %{last} %{first}
+//! ```
+//!
+//! In lrc files the entry is written as `_params first last`.
+//!
+//! Every declared parameter must be used and a translation must not use an undeclared parameter.
+//!
+//! ## Placeholders
+//!
+//! The syntax of the parameters can be chosen to share the files with other ecosystems:
+//!
+//! | `placeholders` | parameter | typed parameter     | reference |
+//! |----------------|-----------|---------------------|-----------|
+//! | `percent`      | `%{name}` | `*{name}`           | `@{key}`  |
+//! | `icu`          | `{name}`  | `{name, typed}`     | `{@key}`  |
+//! | `i18next`      | `{{name}}` (or `{{- name}}`) | `{{name, typed}}` | `$t(key)` or `{{@key}}` |
+//!
+//! ## References
+//!
+//! A reference inserts the text of another message, e.g.:
+//!
+//! ```yaml
+//! app_name:
+//!   en: Typed App
+//! welcome:
+//!   en: Welcome to @{app_name}, %{name}
+//!   de: Willkommen bei @{app_name}, %{name}
+//! ```
+//!
+//! The referenced message is resolved with the fallback of the language of the generated text,
+//! i.e. a german text referenced from an english fallback is still german.
+//! The key is the name of the message (like the function name without prefix), a `.` may be used
+//! instead of the separator.
+//! The parameters of the referenced message are added to the parameters of the message.
+//!
+//! With `percent` placeholders `@{` is new syntax: an existing text containing `@{` is now a reference
+//! (or an unknown reference error) and must be escaped as `@@{`, see [escapes](#escapes).
+//!
+//! ## Terms
+//!
+//! Messages with a key starting with `-` are terms, no functions are generated for them, they can only be referenced.
+//! A term may have grammatical variants per language:
+//!
+//! ```yaml
+//! -brand:
+//!   en: Typed App
+//!   de:
+//!     nominative: die Typ-App
+//!     genitive: der Typ-App
+//! settings:
+//!   en: Settings of @{brand:genitive}
+//!   de: Einstellungen @{brand:genitive}
+//! ```
+//!
+//! In lrc files a variant is written as `de:genitive der Typ-App`.
+//!
+//! The referenced variant must exist in all languages with variants, a language without variants
+//! (like `en` above) uses its text for all variants. Without a variant the first one is used.
+//! The `-` of the term may be omitted in the reference if there is no message with the same name.
+//!
+//! ## Lists
+//!
+//! A message may have a list per language instead of a text:
+//!
+//! ```yaml
+//! weekdays:
+//!   en: [Mon, Tue, Wed, Thu, Fri, Sat, Sun]
+//!   de: [Mo, Di, Mi, Do, Fr, Sa, So]
+//! ```
+//!
+//! In lrc files each item is a line, e.g. `en[] Mon`.
+//!
+//! With `static_str` and `mixed_str` (without parameters) the return type is `&'static [&'static str]`,
+//! otherwise an array of the outputs (e.g. `[String; 7]`). All items share the parameters.
+//!
+//! All lists of a message must have the same length as the one of the source language, that is the default
+//! language (see [global](#global)) or its fallback. A list can't be referenced.
+//!
+//! ## Compounds
+//!
+//! A subtree with `_compound: true` (only in yaml and json files) is a group of messages which are
+//! generated together as a struct:
+//!
+//! ```yaml
+//! email:
+//!   welcome:
+//!     _compound: true
+//!     subject:
+//!       en: Welcome, %{name}
+//!     body:
+//!       en: Your code is %{code}.
+//! ```
+//!
+//! Generated code (additionally to the functions of the messages):
+//! ```rust
+//! # enum Language { En, De }
+//! pub struct EmailWelcome<T> {
+//!     pub subject: T,
+//!     pub body: T,
+//! }
+//!
+//! impl Language {
+//! # } trait LanguageTest {
+//!     fn email_welcome(self, name: &str, code: &str) -> EmailWelcome<String>;
+//! }
+//! ```
+//!
+//! The name of the struct is the path in `PascalCase` (it must differ from the enum and the other
+//! compounds), the fields are named after the messages and the parameters are the ones of all
+//! messages. A compound can't contain a list or sub keys.
+//! There are no `try_prefix` and `prefs_prefix` functions for compounds.
+//!
+//! ## Constants
+//!
+//! A message with a `_type` has typed values instead of texts: integers (`u8`, `i32`, …), `bool`
+//! or the names of the variants of an enum (the type is a path, other types like `String` or `f32` are an error).
+//!
+//! ```yaml
+//! first_day_of_week:
+//!   _type: u8
+//!   en: 0
+//!   de: 1
+//! date_order:
+//!   _type: crate::DateOrder
+//!   en: MonthDayYear
+//!   de: DayMonthYear
+//! ```
+//!
+//! Generated code (once, independent of the builders):
+//! ```rust
+//! # enum DateOrder { MonthDayYear }
+//! # enum Language { En, De }
+//! impl Language {
+//! # } trait LanguageTest {
+//!     fn first_day_of_week(self) -> u8;
+//!     fn date_order(self) -> DateOrder;
+//! }
+//! ```
+//!
+//! The values are checked against the type (e.g. the range of an integer) and a missing language uses
+//! its fallback. A constant can't be referenced, can't be a term and can't be part of a compound.
+//!
+//! ## Escapes
+//!
+//! With `percent` placeholders there are three escapes: `%%{` results in the literal text `%{`, `**{` in `*{`
+//! and `@@{` in `@{`. A `}` outside a parameter is always a literal, e.g. `{%{name}}`.
+//!
+//! These escapes change the meaning of existing texts: `%%{name}` was a literal `%` followed by the parameter `name`,
+//! now it's the literal text `%{name}` (likewise `**{name}`), and `@{` was a literal, now it starts a
+//! [reference](#references). A literal `%` directly before a parameter can be written with a term,
+//! e.g. `@{percent_sign}%{name}` with `-percent_sign: {en: "%"}`.
+//!
+//! With `icu` placeholders `''` is an apostrophe and text starting with `'{` or `'}` is quoted until the next `'`,
+//! e.g. `'{name}'` results in the literal text `{name}`.
+//!
+//! With `i18next` placeholders `{{` always starts a parameter.
+//!
+//! ## Markup
+//!
+//! With `markup = "true"` the messages may contain tags, which wrap a part of the text:
+//!
+//! ```yaml
+//! terms:
+//!   en: Read the <link>terms</link>
+//!   de: Lies die <link>AGB</link>
+//! ```
+//!
+//! A tag is a typed parameter, the argument is a function which gets the built inner content
+//! and returns the input, e.g. `link: impl FnOnce(Html) -> Html`. Thus all builders without an input type will skip these messages.
+//!
+//! The tags must be balanced and all languages of a message must use the same tags.
+//! Only `<name>` and `</name>` with a valid parameter name are tags, all other `<` are literals,
+//! `<<` results in the literal text `<`.
+//!
+//! ## Case transforms
+//!
+//! A str parameter can be transformed with `%{name:upper}`, `%{name:lower}` or `%{name:capitalize}`
+//! (`{name, upper}` with `icu` and `{{name, upper}}` with `i18next` placeholders).
+//!
+//! A whole message can be transformed with a `_transform` entry (`_transform upper` in lrc files),
+//! the constant text is transformed by the macro and the str parameters (without an own transform) at runtime.
+//! With `capitalize` only the first letter of the message is changed.
+//!
+//! ```yaml
+//! settings_title:
+//!   _transform: capitalize
+//!   en: "%{name}'s settings"
+//!   de: straße von %{name}
+//! ```
+//!
+//! The case mapping is the one of unicode (`ß` → `SS`) with the special cases of the dotted and dotless i
+//! for turkish and azerbaijani (as detected by the tag or name of the language).
+//! At runtime [`Builder::push_str_case`] is called, the default implementation uses [`map_case`].
+//!
+//! ## Metadata
+//!
+//! A message may have entries for the translators, which are shown in the documentation of the generated functions
+//! and are available through `typed_i18n_support::messages::Metadata`:
+//!
+//! * `_description`: What the message is about.
+//! * `_note`: A note for the translators.
+//! * `_tags`: A space and/or comma separated list (or a list) of tags.
+//! * `_max_length`: The maximum length of the texts, see [maximum length](#maximum-length) below.
+//!
+//! ```yaml
+//! save:
+//!   _description: The button to save the document
+//!   _tags: [ui, button]
+//!   _max_length: 12
+//!   en: Save
+//!   de: Speichern
+//! ```
+//!
+//! In lrc files they are lines like `_tags ui button`.
+//!
+//! ## Maximum length
+//!
+//! The `_max_length` is checked by the derive macro against the texts each language displays, a violation is a
+//! compile error naming the key and the language. These are the same texts as for the [character set](#character-set):
+//! the own text or the one of the fallback, with the references resolved and the case transform applied
+//! (a term with each of its variants).
+//!
+//! It's a number of chars or a number with the unit `chars`, `bytes` (of the UTF-8 encoding) or `columns`
+//! (east asian wide and fullwidth chars take two columns, combining marks none), e.g. `20 bytes`.
+//! Each parameter is counted with the length of the input field `param_length` (default: `0`) in the unit of the message.
+//!
+//! In yaml and json files a `_max_length` next to sub keys applies to all messages below without an own one:
+//!
+//! ```yaml
+//! lcd:
+//!   _max_length: 16 columns
+//!   status:
+//!     en: Ready
+//!     de: Bereit
+//!   error:
+//!     en: Error %{code}
+//!     de: Fehler %{code}
+//! ```
+//!
+//! ## Character set
+//!
+//! When the texts are displayed with a font that only contains some glyphs (e.g. a bitmap font),
+//! the derive macro can check that the texts each language displays (as for the [maximum length](#maximum-length))
+//! only use those chars, a violation is a compile error listing the unsupported chars.
+//!
+//! The input field `charset` is a space and/or comma separated list of code points (`U+00E4`),
+//! ranges (`U+00C0-U+00FF`), `ascii` (U+0020-U+007E) and `latin1` (`ascii` and U+00A0-U+00FF).
+//! The input field `charset_file` names a file whose chars (except line breaks) are the glyphs.
+//! Both can be combined, and both can be overridden per language (see [language](#language) below).
+//! Languages without any are not checked.
+//!
+//! ```rust
+//! # use typed_i18n::TypedI18N;
+//! #[derive(Copy, Clone, TypedI18N)]
+//! #[typed_i18n(filename = "example.yaml", charset = "ascii")]
+//! #[typed_i18n(builder = "static_str")]
+//! enum Language {
+//!   En,
+//!   #[typed_i18n(charset = "latin1")]
+//!   De,
+//! }
+//! ```
+//!
+//! To create a subset of a font, `typed_i18n_support::messages::Messages::used_chars` returns the chars
+//! of the constant texts each language displays (with fallback and references, without the parameters).
+//!
+//! ## Deprecation and aliases
+//!
+//! A message with `_deprecated` (a note or `true`, `false` is not deprecated, the same in lrc files) generates
+//! functions with `#[deprecated]`.
+//!
+//! The `_aliases` (a space and/or comma separated list or a list) are old keys of a message.
+//! For each alias deprecated functions (and rules of the [named macro](#named-arguments)) are generated, which forward
+//! to the functions of the message. Thus a key can be renamed without breaking the existing callers.
+//!
+//! ```yaml
+//! greeting:
+//!   _aliases: [hello]
+//!   en: Hello
+//! farewell:
+//!   _deprecated: no longer shown
+//!   en: Bye
+//! ```
+//!
+//! Generated code:
+//! ```rust
+//! # struct Language;
+//! impl Language {
+//! # } trait LanguageTest {
+//!     fn greeting(self) -> &'static str;
+//!     #[deprecated(note = "use greeting")]
+//!     fn hello(self) -> &'static str;
+//!     #[deprecated(note = "no longer shown")]
+//!     fn farewell(self) -> &'static str;
+//! }
+//! ```
+//!
 //! # Output
 //!
 //! Fields:
 //!
 //! - `builder`: The name of an item which implements [`Builder`], or a special value.
 //! - `prefix`: Prefix for all functions generated by this builder, default: the empty string.
+//! - `try_prefix`: Generate functions which don't use the fallback, default: not generated.
+//! - `prefs_prefix`: Generate functions with a runtime preference list, default: not generated.
 //! - `str_conversion`: How to convert str parameters, default `ref`.
 //! - `input`: Type of the input (for typed inputs), default: no input.
 //! - `input_conversion`: How to convert the parameter into the input type, default: `into`.
+//! - `bidi_isolation`: Isolate the str parameters in right to left languages, default: `false`.
+//! - `include`: Only generate the functions of these messages, default: all.
+//! - `exclude`: Skip the functions of these messages, default: none.
+//! - `vis`: The visibility of the functions, default: the one of the enum.
+//! - `attributes`: Attributes added to each function, default: none.
+//!
+//! Each generated function is documented with the key, path and parameters of the message and the
+//! text of each language (where a fallback is used it's marked), which is shown by rustdoc and IDEs.
 //!
 //! ## `builder`
 //!
@@ -213,6 +540,81 @@
 //! Using identical prefixes or overlapping names will result in functions with identical names and
 //! thus result in a compile error. This will not be checked by the derive macro.
 //!
+//! ## `include` and `exclude`
+//!
+//! A space and/or comma separated list of glob patterns (`*` matches any text and `?` a single char).
+//! A pattern matches the key of a message (the function name without prefix) or its path in the
+//! yaml/json tree joined with `.`, e.g. `ui_*` or `ui.*`.
+//!
+//! A message is generated when it matches any `include` pattern (or there are none) and no `exclude` pattern:
+//!
+//! ```rust
+//! # use typed_i18n::TypedI18N;
+//! # #[derive(Copy, Clone, TypedI18N)]
+//! # #[typed_i18n(filename = "example.yaml")]
+//! #[typed_i18n(builder = "static_str", prefix = "ui_", include = "hello.*")]
+//! #[typed_i18n(builder = "static_str", prefix = "log_", exclude = "hello.*")]
+//! # enum Language { En, De }
+//! ```
+//!
+//! Generated code:
+//! ```rust
+//! # struct Language;
+//! impl Language {
+//! # } trait LanguageTest {
+//!     fn ui_hello_world(self) -> &'static str;
+//!     fn log_maybe_note(self) -> &'static str;
+//! }
+//! ```
+//!
+//! ## `try_prefix`
+//!
+//! Additionally generate functions which return `None` when the language has no own text for the message
+//! (i.e. the fallback would be used). The name is composed of the `try_prefix`, the `prefix` and the message name.
+//!
+//! ```rust
+//! # use typed_i18n::TypedI18N;
+//! # #[derive(Copy, Clone, TypedI18N)]
+//! # #[typed_i18n(filename = "example.yaml")]
+//! #[typed_i18n(builder = "mixed_str", prefix = "str_", try_prefix = "try_")]
+//! # enum Language { En, De }
+//! ```
+//!
+//! Generated code (in addition to the regular functions):
+//! ```rust
+//! # struct Language;
+//! impl Language {
+//! # } trait LanguageTest {
+//!     fn try_str_hello_world(self) -> Option<&'static str>;
+//!     fn try_str_hello_you(self, name: &str) -> Option<String>;
+//! }
+//! ```
+//!
+//! ## `prefs_prefix`
+//!
+//! Additionally generate functions which select the language at runtime from a list of preferences.
+//! The first language in `preferences` which has an own text for the message is used. If there is none
+//! the first language (with its regular fallback) is used, or the default language (see [global](#global))
+//! if `preferences` is empty. The name is composed of the `prefs_prefix`, the `prefix` and the message name.
+//!
+//! ```rust
+//! # use typed_i18n::TypedI18N;
+//! # #[derive(Copy, Clone, TypedI18N)]
+//! # #[typed_i18n(filename = "example.yaml")]
+//! #[typed_i18n(builder = "mixed_str", prefix = "str_", prefs_prefix = "in_")]
+//! # enum Language { En, De }
+//! ```
+//!
+//! Generated code (in addition to the regular functions):
+//! ```rust
+//! # struct Language;
+//! impl Language {
+//! # } trait LanguageTest {
+//!     fn in_str_hello_world(preferences: &[Language]) -> &'static str;
+//!     fn in_str_hello_you(preferences: &[Language], name: &str) -> String;
+//! }
+//! ```
+//!
 //! ## `str_conversion`
 //!
 //! The conversion for all string (normal) `%{param}` parameters:
@@ -246,14 +648,67 @@
 //!
 //! For `ref` and `as_ref` to work the builder must also implement [`BuilderFromRef`].
 //!
+//! ## `vis` and `attributes`
+//!
+//! By default all functions have the visibility of the enum, `vis` overrides it for the functions of this
+//! builder, e.g. `pub(crate)` or `""` (private).
+//!
+//! The `attributes` are added to each function of this builder, e.g. to gate a builder behind a feature:
+//!
+//! ```rust
+//! # use typed_i18n::TypedI18N;
+//! # #[derive(Copy, Clone, TypedI18N)]
+//! # #[typed_i18n(filename = "example.yaml")]
+//! #[typed_i18n(builder = "static_str", vis = "pub(crate)", attributes = "#[inline] #[must_use]")]
+//! #[typed_i18n(builder = "static_str", prefix = "ui_", attributes = r#"#[cfg(feature = "ui")]"#)]
+//! # pub enum Language { En, De }
+//! ```
+//!
+//! Generated code:
+//! ```rust
+//! # struct Language;
+//! impl Language {
+//! # } trait LanguageTest {
+//!     // pub(crate)
+//!     #[inline]
+//!     #[must_use]
+//!     fn hello_world(self) -> &'static str;
+//!     // pub
+//!     #[cfg(feature = "ui")]
+//!     fn ui_hello_world(self) -> &'static str;
+//! }
+//! ```
+//!
+//! ## `bidi_isolation`
+//!
+//! With `bidi_isolation = "true"` each str parameter (`%{name}`) of a right to left language
+//! (see [language](#language)) is wrapped with [`Builder::push_isolate_start`] and [`Builder::push_isolate_end`].
+//! By default these add the unicode isolation characters FSI (U+2068) and PDI (U+2069), which
+//! keep e.g. a latin username from breaking the punctuation of an arabic message.
+//! A builder may override them (e.g. to use `<bdi>` in html).
+//!
 //! # Language
 //!
 //! The enum values can be annotated with:
 //!
 //! * `name`: The name of the language in the messages file, defaults to the name of the value in `snake_case`.
+//! * `tag`: The [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag, e.g. `de-AT`. Defaults to the name
+//!   with `_` replaced by `-`, if that is a valid tag (`en_au` becomes `en-au`). Otherwise the language has no tag.
 //! * `fallback`: A space and/or comma separated list of language names which defines which language
 //!   should be used when a message is missing. Default: all languages in listing order (not necessary in numerical order).
+//!   The list is always completed with all remaining languages, how depends on the `fallback` input field:
+//!   * `listing` (default): In listing order.
+//!   * `hierarchy`: First the parent languages derived from the name (`zh_hant_tw` → `zh_hant` → `zh`),
+//!     then all others in listing order. Parents which are not a language are skipped. When a parent skips a language
+//!     listed before it or is not a language, a warning shows the whole fallback (reported as the use of a deprecated
+//!     item, an explicit `fallback` with the parents avoids it).
 //! * `default`: Is used for a [global](#global) storage. Only one language may be the default.
+//! * `dir`: The text direction, `ltr` (default) or `rtl`.
+//! * `charset` and `charset_file`: The chars the language can display, instead of the input fields,
+//!   see [character set](#character-set).
+//! * `complete`: Whether the language must have all keys, instead of the input field `require_complete`,
+//!   see [completeness](#completeness).
+//! * `source`: The language which defines the keys for the completeness. Only one language may be the source.
 //!
 //! Example:
 //!
@@ -271,6 +726,177 @@
 //! }
 //! ```
 //!
+//! Or with the derived hierarchy, `en_au` falls back to `en` even though `de` is listed first:
+//!
+//! ```rust
+//! # use typed_i18n::TypedI18N;
+//! #[derive(Copy, Clone, TypedI18N)]
+//! #[typed_i18n(filename = "example.yaml", fallback = "hierarchy")]
+//! #[typed_i18n(builder = "mixed_str", prefix = "str_")]
+//! enum Language {
+//!   De,
+//!   EnAu,
+//!   En,
+//! }
+//! ```
+//!
+//! ## Completeness
+//!
+//! A missing message uses the fallback of the language. For a language with `complete = "true"` (or all
+//! languages with the input field `require_complete = "true"`) the derive macro instead reports a compile
+//! error listing all keys the language lacks. With `"warn"` it's a warning, reported as the use of a
+//! deprecated item (thus `#[allow(deprecated)]` hides it).
+//!
+//! The keys are all keys of the file or, if there is a language with `source = "true"`, only the keys
+//! of that language. With the input field `empty_is_missing = "true"` an empty text (`""`) counts as missing,
+//! also for the source language. This only affects the check, the empty text is still used at runtime.
+//!
+//! ```rust
+//! # use typed_i18n::TypedI18N;
+//! #[derive(Copy, Clone, TypedI18N)]
+//! #[typed_i18n(filename = "example.yaml", require_complete = "true")]
+//! #[typed_i18n(builder = "static_str")]
+//! enum Language {
+//!   #[typed_i18n(source = "true")]
+//!   En,
+//!   De,
+//! }
+//! ```
+//!
+//! ## Languages from the file
+//!
+//! Instead of the derive macro the function-like macro `language_enum!` can be used, it creates the enum
+//! with a variant for each language of the messages file (the name in `PascalCase`):
+//!
+//! ```rust
+//! typed_i18n::language_enum! {
+//!   #[derive(Copy, Clone)]
+//!   #[typed_i18n(filename = "example.yaml")]
+//!   #[typed_i18n(builder = "mixed_str", prefix = "str_")]
+//!   pub enum Language {
+//!     #[typed_i18n(default = "true")]
+//!     De,
+//!   }
+//! }
+//! # let _ = Language::En;
+//! ```
+//!
+//! The listed variants come first, with the annotations above, followed by the other languages of the file
+//! in order of their first use. The enum must derive `Copy` and `Clone` in any case.
+//!
+//! ## Negotiation
+//!
+//! With the input field `negotiate = "true"` a function to select a language from a list of preferences
+//! (e.g. from the browser or the OS) is generated:
+//! ```rust
+//! # enum Language { En, De }
+//! impl Language {
+//! # } trait LanguageTest {
+//!     fn negotiate(preferences: &str) -> Self;
+//! }
+//! ```
+//!
+//! The preferences are in the format of an `Accept-Language` header, e.g. `de-AT, de;q=0.9, en;q=0.5`.
+//! Each preference is truncated until it matches a tag (`de-AT` → `de`), the best weighted match is
+//! returned. If nothing matches the default language (see [global](#global)) is returned.
+//!
+//! ```rust
+//! # use typed_i18n::TypedI18N;
+//! # #[derive(Copy, Clone, PartialEq, Debug, TypedI18N)]
+//! # #[typed_i18n(filename = "example.yaml", negotiate = "true")]
+//! # #[typed_i18n(builder = "static_str")]
+//! # enum Language { En, De }
+//! assert_eq!(Language::negotiate("de-AT, de;q=0.9, en;q=0.5"), Language::De);
+//! assert_eq!(Language::negotiate("fr"), Language::En);
+//! ```
+//!
+//! ## Direction
+//!
+//! With the input field `direction = "true"` a function which returns the text direction of a language is generated:
+//! ```rust
+//! # enum Language { En, De }
+//! impl Language {
+//! # } trait LanguageTest {
+//!     fn direction(self) -> typed_i18n::Direction;
+//! }
+//! ```
+//!
+//! # Named arguments
+//!
+//! The parameters of the generated functions are in the order of their first appearance in the
+//! messages. To avoid mixing them up a macro for calls with named arguments can be generated.
+//!
+//! ```rust
+//! # use typed_i18n::TypedI18N;
+//! mod i18n {
+//! # use typed_i18n::TypedI18N;
+//!   #[derive(Copy, Clone, TypedI18N)]
+//!   #[typed_i18n(filename = "example.yaml", named_macro = "t")]
+//!   #[typed_i18n(builder = "mixed_str", prefix = "str_")]
+//!   pub enum Language { En, De }
+//! }
+//! use i18n::{t, Language};
+//!
+//! assert_eq!(t!(Language::De, str_hello_you, name = "du"), "Hallo du");
+//! ```
+//!
+//! The first argument is the language, the second one the name of a generated function (including the
+//! prefix and optional generics: `hello_you::<String>`), followed by the parameters in any order.
+//! Unknown, duplicate and missing parameters are reported as an error.
+//!
+//! The macro is available in the module of the enum (and with a `pub` enum, in the crate) as
+//! it can't be exported from the crate.
+//!
+//! ## Localize
+//!
+//! With the named macro the values of other types (e.g. errors) can be localized, each variant is
+//! mapped to a message and its fields are the parameters:
+//!
+//! ```rust
+//! # mod i18n {
+//! # use typed_i18n::TypedI18N;
+//! #   #[derive(Copy, Clone, TypedI18N)]
+//! #   #[typed_i18n(filename = "example.yaml", named_macro = "t")]
+//! #   #[typed_i18n(builder = "String", prefix = "str_")]
+//! #   pub enum Language { En, De }
+//! # }
+//! use i18n::{t, Language};
+//! use typed_i18n::Localize;
+//!
+//! #[derive(Localize)]
+//! #[localize(messages = "Language", named_macro = "t", output = "String", prefix = "str_")]
+//! enum Greeting {
+//!   #[localize(key = "hello_world")]
+//!   World,
+//!   #[localize(key = "hello_you")]
+//!   You { name: String },
+//! }
+//!
+//! assert_eq!(Greeting::You { name: "du".to_string() }.localize(Language::De), "Hallo du");
+//! ```
+//!
+//! Generated code:
+//! ```rust
+//! # enum Language { En, De }
+//! # enum Greeting { World }
+//! impl Greeting {
+//! # } trait GreetingTest {
+//!     fn localize(&self, language: Language) -> String;
+//! }
+//! ```
+//!
+//! The attribute of the type has the fields:
+//! * `messages`: The language enum (required).
+//! * `named_macro`: The path to the named macro of the language enum (required).
+//! * `output`: The output type of the builder (required).
+//! * `prefix`: The prefix of the builder, default: none.
+//! * `key`: The key of the message of a struct, for an enum it's at each variant.
+//! * `display`: Implement `Display` with the [global](#global) language, default: `false`.
+//!
+//! The fields are passed as references, they can be annotated with `name` (the name of the parameter,
+//! required for unnamed fields) or `skip = "true"`. Unknown messages, unknown and missing parameters
+//! are reported by the named macro.
+//!
 //! # Global
 //!
 //! It is possible to generate a global language storage.
@@ -322,8 +948,14 @@
 
 #[cfg(feature = "alloc")]
 mod alloc;
+mod case;
+
+pub use case::{map_case, Case, CaseMapping};
 
-pub use typed_i18n_derive::TypedI18N;
+pub use typed_i18n_derive::{language_enum, Localize, TypedI18N};
+
+#[doc(hidden)]
+pub use typed_i18n_derive::__named_call;
 
 /// Trait to create localized strings (from constant messages and string parameters).
 pub trait Builder: Sized {
@@ -359,6 +991,37 @@ pub trait Builder: Sized {
     #[must_use]
     fn push_str(self, i: &str) -> Self;
 
+    /// Add a string with a mapped case to the builder, see [case transforms](crate#case-transforms).
+    ///
+    /// `turkic` is set for languages with the turkic mapping of the dotted and dotless i (`tr` and `az`).
+    #[inline]
+    #[must_use]
+    fn push_str_case(mut self, i: &str, case: Case, turkic: bool) -> Self {
+        let mut buffer = [0; 4];
+        for c in map_case(i, case, turkic) {
+            self = self.push_str(c.encode_utf8(&mut buffer));
+        }
+        self
+    }
+
+    /// Start the isolation of a parameter, see [bidi isolation](crate#bidi_isolation).
+    ///
+    /// Defaults to FIRST STRONG ISOLATE (U+2068).
+    #[inline]
+    #[must_use]
+    fn push_isolate_start(self) -> Self {
+        self.push_const_str("\u{2068}")
+    }
+
+    /// End the isolation of a parameter, see [bidi isolation](crate#bidi_isolation).
+    ///
+    /// Defaults to POP DIRECTIONAL ISOLATE (U+2069).
+    #[inline]
+    #[must_use]
+    fn push_isolate_end(self) -> Self {
+        self.push_const_str("\u{2069}")
+    }
+
     /// Convert the builder into the output.
     #[must_use]
     fn finish(self) -> Self::Output;
@@ -377,3 +1040,77 @@ pub trait BuilderFromValue<Input>: Builder {
     #[must_use]
     fn push(self, i: Input) -> Self;
 }
+
+/// The text direction of a language, see [language](crate#language).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left to right.
+    Ltr,
+    /// Right to left.
+    Rtl,
+}
+
+/// Select the language which matches the preferences best.
+///
+/// The `preferences` are in the format of an `Accept-Language` header, e.g. `de-AT, de;q=0.9, en;q=0.5`.
+/// Each preference is matched with the lookup scheme of RFC 4647, i.e. it is truncated (`de-AT` → `de`)
+/// until a tag matches. The preference with the highest weight (the earlier one on a tie) that
+/// matches wins. The wildcard `*` and preferences with a weight of zero are ignored, a malformed
+/// weight (not `0` to `1` with at most three decimals, see RFC 7231) counts as zero.
+///
+/// This is used by the generated `negotiate` function, see [language](crate#language).
+#[must_use]
+pub fn negotiate<L: Copy>(preferences: &str, tags: &[(&str, L)]) -> Option<L> {
+    let mut best: Option<(f32, L)> = None;
+    for preference in preferences.split(',') {
+        let mut parts = preference.split(';');
+        let range = parts.next().unwrap_or_default().trim();
+        let mut weight = 1.0;
+        for p in parts {
+            let p = p.trim();
+            if let Some(q) = p.strip_prefix("q=").or_else(|| p.strip_prefix("Q=")) {
+                weight = parse_weight(q.trim());
+            }
+        }
+        if range.is_empty() || range == "*" || weight <= 0.0 {
+            continue;
+        }
+        if best.map_or(true, |(w, _)| weight > w) {
+            if let Some(l) = lookup(range, tags) {
+                best = Some((weight, l));
+            }
+        }
+    }
+    best.map(|(_, l)| l)
+}
+
+/// A `qvalue`: `0` to `1` with at most three decimals, otherwise zero.
+fn parse_weight(q: &str) -> f32 {
+    let (int, frac) = q.split_once('.').unwrap_or((q, ""));
+    let valid = frac.len() <= 3
+        && match int {
+            "0" => frac.bytes().all(|b| b.is_ascii_digit()),
+            "1" => frac.bytes().all(|b| b == b'0'),
+            _ => false,
+        };
+    if valid {
+        q.parse().unwrap_or(0.0)
+    } else {
+        0.0
+    }
+}
+
+fn lookup<L: Copy>(mut range: &str, tags: &[(&str, L)]) -> Option<L> {
+    loop {
+        if let Some((_, l)) = tags.iter().find(|(t, _)| t.eq_ignore_ascii_case(range)) {
+            return Some(*l);
+        }
+        range = &range[..range.rfind('-')?];
+        // a single character subtag (an extension or private use marker) is removed as well
+        if let Some(pos) = range.rfind('-') {
+            if range.len() - pos == 2 {
+                range = &range[..pos];
+            }
+        }
+    }
+}
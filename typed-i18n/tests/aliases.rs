@@ -0,0 +1,47 @@
+#![cfg(feature = "alloc")]
+#![allow(deprecated)]
+use crate::derive::{t, Language};
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/aliases.yaml", named_macro = "t")]
+    #[typed_i18n(builder = "mixed_str", try_prefix = "try_", prefs_prefix = "in_")]
+    #[typed_i18n(builder = "_", prefix = "gen_")]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+#[test]
+fn aliases() {
+    assert_eq!(Language::De.hello(), "Hallo");
+    assert_eq!(Language::De.hi(), "Hallo");
+    assert_eq!(Language::En.hello_you("you"), "Hello you");
+    assert_eq!(Language::De.gen_hello_you::<String>("du"), "Hallo du");
+    assert_eq!(Language::En.items_per_page(), 20);
+}
+
+#[test]
+fn try_prefs_aliases() {
+    assert_eq!(Language::De.try_hello(), Some("Hallo"));
+    assert_eq!(Language::in_hello_you(&[Language::De], "du"), "Hallo du");
+}
+
+#[test]
+fn named_aliases() {
+    assert_eq!(t!(Language::En, hi), "Hello");
+    assert_eq!(t!(Language::De, hello_you, name = "du"), "Hallo du");
+}
+
+#[test]
+fn deprecated() {
+    assert_eq!(Language::De.farewell(), "Bye");
+    assert_eq!(Language::De.try_farewell(), None);
+    assert_eq!(Language::in_farewell(&[Language::De]), "Bye");
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "alloc")]
+use crate::common::{Element, Output};
+use crate::derive::Language;
+use typed_i18n::Direction;
+
+mod common;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use crate::common::Tester;
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/bidi.yaml", direction = "true")]
+    #[typed_i18n(builder = "mixed_str", bidi_isolation = "true")]
+    #[typed_i18n(builder = "Tester<String>", prefix = "t_")]
+    pub enum Language {
+        En,
+        #[typed_i18n(dir = "rtl")]
+        Ar,
+    }
+}
+
+#[test]
+fn direction() {
+    assert_eq!(Language::En.direction(), Direction::Ltr);
+    assert_eq!(Language::Ar.direction(), Direction::Rtl);
+}
+
+#[test]
+fn bidi_isolation() {
+    assert_eq!(Language::En.hello("Bob"), "Hello Bob!");
+    assert_eq!(Language::Ar.hello("Bob"), "مرحبا \u{2068}Bob\u{2069}!");
+    // also in the fallback text
+    assert_eq!(Language::En.bye("Bob"), "Bye Bob");
+    assert_eq!(Language::Ar.bye("Bob"), "Bye \u{2068}Bob\u{2069}");
+    // only when enabled
+    assert_eq!(
+        Language::Ar.t_hello("Bob"),
+        Output::Built(vec![
+            Element::Const("مرحبا "),
+            Element::String("Bob".to_string()),
+            Element::Const("!"),
+        ])
+    );
+}
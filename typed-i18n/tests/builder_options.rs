@@ -0,0 +1,41 @@
+use crate::derive::Language;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "example.yaml")]
+    #[typed_i18n(
+        builder = "static_str",
+        vis = "pub(crate)",
+        attributes = "#[inline] #[must_use]",
+        try_prefix = "try_"
+    )]
+    #[typed_i18n(builder = "_", prefix = "gated_", attributes = "#[cfg(any())]")]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+/// Inherent functions are preferred, the trait functions are only used if a function is not generated.
+trait Missing: Sized {
+    fn gated_hello_world(self) -> &'static str {
+        "missing"
+    }
+}
+
+impl Missing for Language {}
+
+#[test]
+fn vis_and_attributes() {
+    assert_eq!(Language::En.hello_world(), "Hello world");
+    assert_eq!(Language::De.try_hello_world(), Some("Hallo Welt"));
+}
+
+#[test]
+fn cfg_attribute() {
+    assert_eq!(Language::En.gated_hello_world(), "missing");
+}
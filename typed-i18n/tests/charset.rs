@@ -0,0 +1,26 @@
+use crate::derive::Language;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/charset.yaml", charset = "ascii")]
+    #[typed_i18n(builder = "static_str")]
+    pub enum Language {
+        En,
+        #[typed_i18n(charset = "latin1")]
+        De,
+        #[typed_i18n(charset_file = "tests/charset_fr.txt")]
+        Fr,
+    }
+}
+
+#[test]
+fn charset() {
+    assert_eq!(Language::En.greet(), "Hello");
+    assert_eq!(Language::De.greet(), "Grüße");
+    assert_eq!(Language::Fr.greet(), "Bonjour");
+    assert_eq!(Language::Fr.street(), "RUE DES ÉCOLES");
+}
@@ -0,0 +1,12 @@
+use typed_i18n_derive::TypedI18N;
+
+#[derive(Copy, Clone, TypedI18N)]
+#[typed_i18n(filename = "tests/compile-fail/charset.yaml", charset = "ascii")]
+#[typed_i18n(builder = "static_str")]
+enum Language {
+    En,
+    #[typed_i18n(charset_file = "tests/compile-fail/charset_de.txt")]
+    De,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use typed_i18n_derive::TypedI18N;
+
+#[derive(Copy, Clone, TypedI18N)]
+#[typed_i18n(filename = "tests/compile-fail/complete.yaml", empty_is_missing = "true")]
+#[typed_i18n(builder = "static_str")]
+enum Language {
+    #[typed_i18n(source = "true")]
+    En,
+    #[typed_i18n(complete = "true")]
+    De,
+    Fr,
+}
+
+fn main() {}
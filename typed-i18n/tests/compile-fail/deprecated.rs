@@ -0,0 +1,17 @@
+#![deny(deprecated)]
+
+use typed_i18n_derive::TypedI18N;
+
+#[derive(Copy, Clone, TypedI18N)]
+#[typed_i18n(filename = "tests/aliases.yaml")]
+#[typed_i18n(builder = "static_str")]
+#[typed_i18n(builder = "mixed_str", prefix = "str_", try_prefix = "try_", prefs_prefix = "in_")]
+enum Language {
+    En,
+    De,
+}
+
+fn main() {
+    Language::En.hello();
+    Language::En.farewell();
+}
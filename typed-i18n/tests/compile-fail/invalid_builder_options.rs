@@ -0,0 +1,11 @@
+use typed_i18n_derive::TypedI18N;
+
+#[derive(Copy, Clone, TypedI18N)]
+#[typed_i18n(filename = "example.yaml")]
+#[typed_i18n(builder = "static_str", vis = "pub(", attributes = "inline")]
+enum Language {
+    En,
+    De,
+}
+
+fn main() {}
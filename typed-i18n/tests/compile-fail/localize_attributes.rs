@@ -0,0 +1,19 @@
+use typed_i18n_derive::{Localize, TypedI18N};
+
+#[derive(Copy, Clone, TypedI18N)]
+#[typed_i18n(filename = "example.yaml", named_macro = "t")]
+#[typed_i18n(builder = "String")]
+enum Language {
+    En,
+    De,
+}
+
+#[derive(Localize)]
+#[localize(messages = "Language", named_macro = "t", output = "String")]
+enum Greeting {
+    Unknown,
+    #[localize(key = "hello_you")]
+    Unnamed(String),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use typed_i18n_derive::TypedI18N;
+
+#[derive(Copy, Clone, TypedI18N)]
+#[typed_i18n(filename = "tests/compile-fail/max_length.yaml", param_length = "6")]
+#[typed_i18n(builder = "static_str")]
+enum Language {
+    En,
+    De,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use typed_i18n_derive::TypedI18N;
+
+#[derive(Copy, Clone, TypedI18N)]
+#[typed_i18n(filename = "example.yaml", named_macro = "t")]
+#[typed_i18n(builder = "mixed_str")]
+enum Language {
+    En,
+    De,
+}
+
+fn main() {
+    let _ = t!(Language::En, hello_you);
+    let _ = t!(Language::En, hello_you, name = "you", nam = "you");
+    let _ = t!(Language::En, hello_you, name = "you", name = "me");
+    let _ = t!(Language::En, hallo_world);
+}
@@ -0,0 +1,12 @@
+use typed_i18n_derive::TypedI18N;
+
+#[derive(Copy, Clone, TypedI18N)]
+#[typed_i18n(filename = "example.yaml")]
+#[typed_i18n(builder = "static_str")]
+enum Language {
+    En,
+    #[typed_i18n(dir = "up")]
+    De,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use typed_i18n_derive::TypedI18N;
+
+#[derive(Copy, Clone, TypedI18N)]
+#[typed_i18n(filename = "example.yaml", fallback = "random")]
+#[typed_i18n(builder = "static_str")]
+enum Language {
+    En,
+}
+
+fn main() {}
@@ -0,0 +1,53 @@
+#![cfg(feature = "alloc")]
+use crate::derive::{EmailFooter, EmailWelcome, Language};
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/compound.yaml", named_macro = "t")]
+    #[typed_i18n(builder = "static_str", prefix = "static_")]
+    #[typed_i18n(builder = "mixed_str")]
+    pub enum Language {
+        En,
+        De,
+    }
+
+    #[test]
+    fn named() {
+        ::std::assert_eq!(
+            t!(Language::En, email_welcome, code = "42", name = "Bob").body,
+            "Your code is 42."
+        );
+    }
+}
+
+#[test]
+fn compound() {
+    assert_eq!(
+        Language::De.email_welcome("Bob", "42"),
+        EmailWelcome {
+            subject: "Willkommen, Bob".to_string(),
+            body: "Dein Code ist 42.".to_string(),
+            button: "Anmelden".to_string(),
+        }
+    );
+    // the messages are still available
+    assert_eq!(Language::En.email_welcome_button(), "Sign in");
+}
+
+#[test]
+fn static_compound() {
+    let footer: EmailFooter<&'static str> = Language::De.static_email_footer();
+    assert_eq!(
+        footer,
+        EmailFooter {
+            legal: "All rights reserved",
+            unsubscribe: "Abbestellen",
+        }
+    );
+    assert_eq!(Language::De.email_footer().unsubscribe, "Abbestellen");
+}
@@ -0,0 +1,47 @@
+use crate::derive::{DateOrder, Language, Measurement};
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum DateOrder {
+        DayMonthYear,
+        MonthDayYear,
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Measurement {
+        Metric,
+        Imperial,
+    }
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/constants.yaml")]
+    #[typed_i18n(builder = "static_str", prefix = "static_")]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+#[test]
+fn scalars() {
+    let day: u8 = Language::De.first_day_of_week();
+    assert_eq!(day, 1);
+    assert_eq!(Language::En.first_day_of_week(), 0);
+    assert!(Language::En.currency_before_amount());
+    assert!(!Language::De.currency_before_amount());
+    assert_eq!(Language::En.utc_offset(), -5);
+    assert_eq!(Language::De.utc_offset(), 1);
+}
+
+#[test]
+fn enum_variants() {
+    assert_eq!(Language::En.date_order(), DateOrder::MonthDayYear);
+    assert_eq!(Language::De.date_order(), DateOrder::DayMonthYear);
+    assert_eq!(Language::De.measurement(), Measurement::Imperial);
+    assert_ne!(Language::En.measurement(), Measurement::Metric);
+    assert_eq!(Language::De.static_hello(), "Hallo");
+}
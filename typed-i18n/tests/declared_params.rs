@@ -0,0 +1,40 @@
+#![cfg(feature = "alloc")]
+use crate::common::{Element, Output};
+use crate::derive::Language;
+
+mod common;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use crate::common::Tester;
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/declared_params.yaml")]
+    #[typed_i18n(builder = "mixed_str")]
+    #[typed_i18n(builder = "Tester<bool>", input = "bool", prefix = "t_")]
+    pub enum Language {
+        En,
+        Ja,
+    }
+}
+
+#[test]
+fn declared_params() {
+    // the order is as declared and not as first used (by ja)
+    assert_eq!(Language::En.hello_full("John", "Doe"), "Hello John Doe");
+    assert_eq!(
+        Language::Ja.hello_full("John", "Doe"),
+        "こんにちは Doe John"
+    );
+    assert_eq!(
+        Language::Ja.t_hello_icon("you", true),
+        Output::Built(vec![
+            Element::T(true),
+            Element::Const(" Hello "),
+            Element::String("you".to_string()),
+        ])
+    );
+}
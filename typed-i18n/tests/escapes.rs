@@ -0,0 +1,53 @@
+#![cfg(feature = "alloc")]
+use crate::common::{Element, Output};
+use crate::derive::Language;
+
+mod common;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use crate::common::Tester;
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/escapes.lrc")]
+    #[typed_i18n(builder = "mixed_str")]
+    #[typed_i18n(builder = "Tester<()>", prefix = "t_")]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+#[test]
+fn escapes() {
+    assert_eq!(
+        Language::En.template_help(),
+        "Use %{name} for a text and *{name} for a value"
+    );
+    assert_eq!(
+        Language::De.t_template_help(),
+        Output::Const("Nutze %{name} für einen Text und *{name} für einen Wert")
+    );
+    assert_eq!(Language::En.percent("x"), "100%{x}");
+    assert_eq!(Language::De.percent("x"), "x: 100%{");
+    assert_eq!(
+        Language::En.t_percent("x"),
+        Output::Built(vec![
+            Element::Const("100%{"),
+            Element::String("x".to_string()),
+            Element::Const("}"),
+        ])
+    );
+    // a literal % before a parameter
+    assert_eq!(Language::En.discount("20"), "%20 off");
+    assert_eq!(Language::De.discount("20"), "%20 Rabatt");
+    // not a reference
+    assert_eq!(Language::En.mention(), "Write @{name} to mention someone");
+    assert_eq!(
+        Language::De.t_mention(),
+        Output::Const("Schreibe @{name} um jemanden zu erwähnen")
+    );
+}
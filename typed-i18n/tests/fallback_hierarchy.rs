@@ -0,0 +1,42 @@
+mod listing {
+    #![no_implicit_prelude]
+
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "example.yaml")]
+    #[typed_i18n(builder = "static_str")]
+    pub enum Language {
+        De,
+        EnAu,
+        En,
+    }
+}
+
+mod hierarchy {
+    #![no_implicit_prelude]
+    // the fallback of en_au skips de, which is reported as a warning
+    #![allow(deprecated)]
+
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "example.yaml", fallback = "hierarchy")]
+    #[typed_i18n(builder = "static_str")]
+    pub enum Language {
+        De,
+        EnAu,
+        En,
+    }
+}
+
+#[test]
+fn fallback_hierarchy() {
+    assert_eq!(listing::Language::De.hello_world(), "Hallo Welt");
+    assert_eq!(listing::Language::EnAu.hello_world(), "Hallo Welt");
+    assert_eq!(listing::Language::En.hello_world(), "Hello world");
+
+    assert_eq!(hierarchy::Language::De.hello_world(), "Hallo Welt");
+    assert_eq!(hierarchy::Language::EnAu.hello_world(), "Hello world");
+    assert_eq!(hierarchy::Language::En.hello_world(), "Hello world");
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "alloc")]
+use crate::derive::Language;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "example.yaml")]
+    #[typed_i18n(builder = "static_str", prefix = "ui_", include = "hello.*")]
+    #[typed_i18n(builder = "mixed_str", prefix = "log_", exclude = "hello_*")]
+    #[typed_i18n(
+        builder = "::std::string::String",
+        prefix = "both_",
+        include = "hello_* maybe_*",
+        exclude = "*_world"
+    )]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+/// Inherent functions are preferred, the trait functions are only used if a function is not generated.
+trait Missing: Sized {
+    fn ui_maybe_note(self) -> &'static str {
+        "missing"
+    }
+    fn log_hello_world(self) -> &'static str {
+        "missing"
+    }
+    fn both_hello_world(self) -> &'static str {
+        "missing"
+    }
+}
+
+impl Missing for Language {}
+
+#[test]
+fn include() {
+    assert_eq!(Language::En.ui_hello_world(), "Hello world");
+    assert_eq!(Language::En.ui_maybe_note(), "missing");
+}
+
+#[test]
+fn exclude() {
+    assert_eq!(Language::De.log_maybe_note(), "Hinweis");
+    assert_eq!(Language::De.log_hello_world(), "missing");
+}
+
+#[test]
+fn include_and_exclude() {
+    assert_eq!(Language::De.both_maybe_note(), "Hinweis");
+    assert_eq!(Language::De.both_hello_you("du"), "Hallo du");
+    assert_eq!(Language::De.both_hello_world(), "missing");
+}
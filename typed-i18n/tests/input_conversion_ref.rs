@@ -1,4 +1,4 @@
-use crate::common::{Element, Output};
+use crate::common::{Element, Output, Tester};
 use crate::derive::Language;
 
 mod common;
@@ -20,6 +20,21 @@ mod derive {
     }
 }
 
+mod generic {
+    #![no_implicit_prelude]
+
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "example.yaml")]
+    #[typed_i18n(builder = "_", input = "str", input_conversion = "ref")]
+    pub enum Language {
+        En,
+        #[allow(dead_code)]
+        De,
+    }
+}
+
 #[test]
 fn input_conversion_ref() {
     assert_eq!(
@@ -31,3 +46,15 @@ fn input_conversion_ref() {
         ])
     );
 }
+
+#[test]
+fn input_conversion_ref_generic() {
+    assert_eq!(
+        generic::Language::En.hello_you_w_icon::<Tester<String>>("you", "🤩"),
+        Output::Built(vec![
+            Element::Const("Hello "),
+            Element::String("you".to_string()),
+            Element::T("🤩".to_string()),
+        ])
+    );
+}
@@ -0,0 +1,27 @@
+use crate::generated::Language;
+
+mod generated {
+    #![no_implicit_prelude]
+
+    ::typed_i18n::language_enum! {
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        #[typed_i18n(filename = "tests/language_enum.yaml", negotiate = "true")]
+        #[typed_i18n(builder = "static_str")]
+        pub enum Language {
+            /// The fallback of Austrian German is German.
+            #[typed_i18n(fallback = "de")]
+            DeAt,
+        }
+    }
+}
+
+#[test]
+fn variants() {
+    // the listed variants come first, then the languages of the file
+    assert_eq!(Language::DeAt.hello(), "Servus");
+    assert_eq!(Language::DeAt.bye(), "Tschüss");
+    assert_eq!(Language::En.bye(), "Bye");
+    assert_eq!(Language::De.hello(), "Hallo");
+    assert_eq!(Language::negotiate("fr"), Language::DeAt);
+    assert_eq!(Language::negotiate("de-AT, en"), Language::DeAt);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "alloc")]
+use crate::common::{Element, Output};
+use crate::derive::Language;
+
+mod common;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use crate::common::Tester;
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/lists.yaml")]
+    #[typed_i18n(builder = "static_str", prefix = "static_")]
+    #[typed_i18n(builder = "mixed_str", try_prefix = "try_", prefs_prefix = "in_")]
+    #[typed_i18n(builder = "Tester<()>", prefix = "t_")]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+#[test]
+fn static_list() {
+    let weekdays: &'static [&'static str] = Language::De.static_weekdays();
+    assert_eq!(weekdays, &["Mo", "Di", "Mi"]);
+    assert_eq!(Language::En.weekdays(), &["Mon", "Tue", "Wed"]);
+}
+
+#[test]
+fn built_list() {
+    let steps: [String; 2] = Language::De.steps("Hi");
+    assert_eq!(steps, ["Sign up".to_string(), "Say Hi".to_string()]);
+    assert_eq!(
+        Language::En.tips("du"),
+        [String::new(), "NUR DU".to_string()]
+    );
+    assert_eq!(
+        Language::En.t_steps("Hi"),
+        [
+            Output::Const("Sign up"),
+            Output::Built(vec![
+                Element::Const("Say "),
+                Element::String("Hi".to_string())
+            ])
+        ]
+    );
+}
+
+#[test]
+fn try_prefs_list() {
+    assert_eq!(Language::De.try_weekdays(), Some(&["Mo", "Di", "Mi"][..]));
+    assert_eq!(Language::De.try_steps("Hi"), None);
+    assert_eq!(
+        Language::in_tips(&[Language::En, Language::De], "x"),
+        [String::new(), "NUR X".to_string()]
+    );
+}
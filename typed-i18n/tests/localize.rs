@@ -0,0 +1,88 @@
+#![cfg(feature = "alloc")]
+use crate::derive::{Error, Language, Limit};
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::std::string::String;
+    use ::typed_i18n::{Localize, TypedI18N};
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/localize.yaml", named_macro = "t", global = "atomic")]
+    #[typed_i18n(builder = "String", prefix = "str_")]
+    pub enum Language {
+        En,
+        De,
+    }
+
+    #[derive(Localize)]
+    #[localize(
+        messages = "Language",
+        named_macro = "t",
+        output = "String",
+        prefix = "str_",
+        display = "true"
+    )]
+    pub enum Error {
+        #[localize(key = "errors_not_found")]
+        NotFound {
+            path: String,
+            #[localize(skip = "true")]
+            code: u16,
+        },
+        #[localize(key = "errors_denied")]
+        Denied,
+        #[localize(key = "errors_limit")]
+        Limit(
+            #[localize(name = "kind")] &'static str,
+            #[localize(skip = "true")] u16,
+            #[localize(name = "max")] String,
+        ),
+    }
+
+    #[derive(Localize)]
+    #[localize(
+        messages = "Language",
+        named_macro = "t",
+        output = "String",
+        prefix = "str_",
+        key = "errors_limit"
+    )]
+    pub struct Limit {
+        pub max: &'static str,
+        pub kind: &'static str,
+        #[localize(skip = "true")]
+        pub language: Language,
+    }
+}
+
+#[test]
+fn localize_enum() {
+    let error = Error::NotFound {
+        path: "/tmp".to_string(),
+        code: 404,
+    };
+    assert_eq!(error.localize(Language::De), "/tmp nicht gefunden");
+    assert!(matches!(error, Error::NotFound { code: 404, .. }));
+    assert_eq!(Error::Denied.localize(Language::En), "Access denied");
+    let error = Error::Limit("files", 0, "7".to_string());
+    assert_eq!(error.localize(Language::En), "More than 7 of files");
+    assert!(matches!(error, Error::Limit(_, 0, _)));
+}
+
+#[test]
+fn localize_struct() {
+    let limit = Limit {
+        max: "3",
+        kind: "Dateien",
+        language: Language::En,
+    };
+    assert_eq!(limit.localize(Language::De), "Mehr als 3 Dateien");
+    assert_eq!(limit.localize(limit.language), "More than 3 of Dateien");
+}
+
+#[test]
+fn display() {
+    Language::De.set_global();
+    assert_eq!(Error::Denied.to_string(), "Zugriff verweigert");
+}
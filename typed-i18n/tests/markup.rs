@@ -0,0 +1,60 @@
+#![cfg(feature = "alloc")]
+use crate::common::{Element, Output};
+use crate::derive::Language;
+use std::format;
+
+mod common;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use crate::common::Tester;
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/markup.yaml", markup = "true")]
+    #[typed_i18n(builder = "mixed_str")]
+    #[typed_i18n(builder = "Tester<String>", input = "String", prefix = "t_")]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+fn wrap(tag: &'static str) -> impl FnOnce(Output<String>) -> String {
+    move |inner| format!("<{tag}>{inner:?}</{tag}>")
+}
+
+#[test]
+fn markup() {
+    assert_eq!(
+        Language::En.t_terms(wrap("a")),
+        Output::Built(vec![
+            Element::Const("Read the "),
+            Element::T("<a>Const(\"terms\")</a>".to_string()),
+        ])
+    );
+    assert_eq!(
+        Language::De.t_terms(wrap("a")),
+        Output::Built(vec![
+            Element::Const("Lies die "),
+            Element::T("<a>Const(\"AGB\")</a>".to_string()),
+            Element::Const(" genau"),
+        ])
+    );
+    // the order of the parameters is the order of the first use (en)
+    assert_eq!(
+        Language::De.t_welcome(wrap("b"), wrap("i"), "du", wrap("a")),
+        Output::Built(vec![
+            Element::T("<a>Const(\"Hier\")</a>".to_string()),
+            Element::Const(": "),
+            Element::T(
+                "<b>Built([Const(\"Hallo \"), T(\"<i>Built([String(\\\"du\\\")])</i>\")])</b>"
+                    .to_string()
+            ),
+        ])
+    );
+    // no tags, just text
+    assert_eq!(Language::En.compare(), "1 < 2 and a < b, but <not a tag>");
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "alloc")]
+use crate::common::{Element, Output};
+use crate::derive::{t, Language};
+
+mod common;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use crate::common::Tester;
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "example.yaml", named_macro = "t")]
+    #[typed_i18n(builder = "mixed_str", prefix = "str_")]
+    #[typed_i18n(builder = "Tester<bool>", input = "bool")]
+    #[typed_i18n(builder = "_", prefix = "gen_")]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+#[test]
+fn named_macro() {
+    assert_eq!(t!(Language::En, str_hello_world), "Hello world");
+    assert_eq!(t!(Language::De, str_hello_you, name = "du"), "Hallo du");
+    let name = String::from("you");
+    assert_eq!(
+        t!(Language::En, hello_you_w_icon, icon = true, name = &name),
+        Output::Built(vec![
+            Element::Const("Hello "),
+            Element::String("you".to_string()),
+            Element::T(true),
+        ])
+    );
+    assert_eq!(
+        t!(Language::En, gen_hello_you::<String>, name = name.as_str(),),
+        "Hello you"
+    );
+}
@@ -0,0 +1,71 @@
+use crate::derive::Language;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, PartialEq, Debug, TypedI18N)]
+    #[typed_i18n(filename = "example.yaml", negotiate = "true")]
+    #[typed_i18n(builder = "static_str")]
+    pub enum Language {
+        En,
+        #[typed_i18n(default = "true")]
+        De,
+        #[typed_i18n(tag = "de-AT")]
+        Austrian,
+        #[typed_i18n(name = "en_x", tag = "en-GB")]
+        British,
+    }
+}
+
+#[test]
+fn negotiate() {
+    assert_eq!(
+        Language::negotiate("de-AT, de;q=0.9, en;q=0.5"),
+        Language::Austrian
+    );
+    assert_eq!(Language::negotiate("de-DE, en;q=0.5"), Language::De);
+    assert_eq!(Language::negotiate("en-gb-oed"), Language::British);
+    assert_eq!(Language::negotiate("en-GB;q=0.5, en"), Language::En);
+    assert_eq!(Language::negotiate("en-GB;q=0, en-US"), Language::En);
+    assert_eq!(Language::negotiate("en-x-private"), Language::En);
+    // nothing matches, the default is returned
+    assert_eq!(Language::negotiate("fr, *"), Language::De);
+    assert_eq!(Language::negotiate(""), Language::De);
+}
+
+#[test]
+fn negotiate_function() {
+    let tags = [("de", 1), ("de-ch", 2)];
+    assert_eq!(typed_i18n::negotiate("de-CH-1996", &tags), Some(2));
+    assert_eq!(typed_i18n::negotiate("fr;q=1, de;q=0.1", &tags), Some(1));
+    assert_eq!(
+        typed_i18n::negotiate("de;q=0.1, de-ch;q=0.2", &tags),
+        Some(2)
+    );
+    assert_eq!(typed_i18n::negotiate("fr", &tags), None);
+}
+
+#[test]
+fn negotiate_malformed_weights() {
+    let tags = [("de", 1), ("de-ch", 2), ("en", 3)];
+    // a malformed weight counts as zero and doesn't block the later preferences
+    assert_eq!(typed_i18n::negotiate("de;q=NaN, en;q=0.5", &tags), Some(3));
+    assert_eq!(typed_i18n::negotiate("de;q=inf, en;q=0.5", &tags), Some(3));
+    assert_eq!(typed_i18n::negotiate("de;q=5, en;q=0.5", &tags), Some(3));
+    assert_eq!(typed_i18n::negotiate("de;q=1.5, en;q=0.5", &tags), Some(3));
+    assert_eq!(
+        typed_i18n::negotiate("de;q=0.1234, en;q=0.5", &tags),
+        Some(3)
+    );
+    assert_eq!(typed_i18n::negotiate("de;q=-1, en;q=0.5", &tags), Some(3));
+    assert_eq!(typed_i18n::negotiate("de;q=x", &tags), None);
+    // valid weights
+    assert_eq!(
+        typed_i18n::negotiate("en;q=0.5, de;q=1.000", &tags),
+        Some(1)
+    );
+    assert_eq!(typed_i18n::negotiate("en;q=0.999, de;q=1.", &tags), Some(1));
+    assert_eq!(typed_i18n::negotiate("en;q=0.001, de;q=0", &tags), Some(3));
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "alloc")]
+use crate::common::{Element, Output};
+use crate::derive::{LanguageI18next, LanguageIcu};
+
+mod common;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use crate::common::Tester;
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/placeholders_icu.yaml", placeholders = "icu")]
+    #[typed_i18n(builder = "mixed_str")]
+    #[typed_i18n(builder = "Tester<bool>", input = "bool", prefix = "t_")]
+    pub enum LanguageIcu {
+        En,
+        De,
+    }
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/placeholders_i18next.json", placeholders = "i18next")]
+    #[typed_i18n(builder = "mixed_str")]
+    #[typed_i18n(builder = "Tester<bool>", input = "bool", prefix = "t_")]
+    pub enum LanguageI18next {
+        En,
+        De,
+    }
+}
+
+#[test]
+fn placeholders_icu() {
+    assert_eq!(LanguageIcu::En.hello_you("you"), "Hello you");
+    assert_eq!(LanguageIcu::De.hello_you("du"), "Hallo du");
+    assert_eq!(
+        LanguageIcu::De.t_hello_you_w_icon("du", true),
+        Output::Built(vec![
+            Element::Const("Hallo "),
+            Element::String("du".to_string()),
+            Element::T(true),
+        ])
+    );
+    assert_eq!(
+        LanguageIcu::En.quoted(),
+        "It's written as {name} or '{name}'"
+    );
+    assert_eq!(
+        LanguageIcu::De.quoted(),
+        "{ and } are literals, ' is an apostrophe"
+    );
+    assert_eq!(LanguageIcu::De.reference("du"), "Hallo du!");
+}
+
+#[test]
+fn placeholders_i18next() {
+    assert_eq!(LanguageI18next::En.hello_you("you"), "Hello you");
+    assert_eq!(LanguageI18next::De.hello_you("du"), "Hallo du");
+    assert_eq!(
+        LanguageI18next::De.t_hello_you_w_icon("du", false),
+        Output::Built(vec![
+            Element::Const("Hallo "),
+            Element::String("du".to_string()),
+            Element::T(false),
+        ])
+    );
+    assert_eq!(LanguageI18next::En.braces("x"), "{name} and x}");
+    assert_eq!(LanguageI18next::En.reference("you"), "Hello you!");
+    assert_eq!(LanguageI18next::De.reference("du"), "Hallo du!");
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "alloc")]
+use crate::common::{Element, Output};
+use crate::derive::Language;
+
+mod common;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use crate::common::Tester;
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/references.yaml")]
+    #[typed_i18n(builder = "mixed_str", try_prefix = "try_")]
+    #[typed_i18n(builder = "Tester<bool>", input = "bool", prefix = "t_")]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+#[test]
+fn references() {
+    assert_eq!(Language::En.welcome(), "Welcome to Typed App");
+    assert_eq!(Language::De.welcome(), "Willkommen bei Typisierte App");
+    // the referenced message uses the fallback of the target language
+    assert_eq!(
+        Language::De.greeting("du"),
+        "Willkommen bei Typisierte App, du"
+    );
+    assert_eq!(Language::De.try_greeting("du"), None);
+    assert_eq!(Language::En.dialog(), "[OK]");
+    assert_eq!(Language::De.dialog(), "[Okay]");
+    assert_eq!(
+        Language::De.t_signed(true, "du"),
+        Output::Built(vec![
+            Element::T(true),
+            Element::Const(" Willkommen bei Typisierte App, "),
+            Element::String("du".to_string()),
+        ])
+    );
+}
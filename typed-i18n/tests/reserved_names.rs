@@ -0,0 +1,23 @@
+use crate::derive::Language;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::typed_i18n::TypedI18N;
+
+    // without the opt-in functions the messages may use their names
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/reserved_names.yaml")]
+    #[typed_i18n(builder = "static_str")]
+    pub enum Language {
+        En,
+        De,
+    }
+}
+
+#[test]
+fn reserved_names() {
+    assert_eq!(Language::En.negotiate(), "Negotiate");
+    assert_eq!(Language::De.negotiate(), "Verhandeln");
+    assert_eq!(Language::En.direction(), "Direction");
+}
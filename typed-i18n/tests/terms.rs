@@ -0,0 +1,39 @@
+use crate::derive::{Language, LanguageLrc};
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/terms.yaml")]
+    #[typed_i18n(builder = "static_str")]
+    pub enum Language {
+        En,
+        De,
+        Pl,
+    }
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/terms.lrc")]
+    #[typed_i18n(builder = "static_str", prefix = "lrc_")]
+    pub enum LanguageLrc {
+        En,
+        De,
+    }
+}
+
+#[test]
+fn terms() {
+    assert_eq!(Language::En.about(), "About Typed App");
+    assert_eq!(Language::De.about(), "Über der Typ-App");
+    assert_eq!(Language::Pl.about(), "O Aplikacji");
+    assert_eq!(Language::En.settings(), "Settings of Typed App");
+    assert_eq!(Language::De.settings(), "Einstellungen der Typ-App");
+    // the text of the fallback with the variant of the language
+    assert_eq!(Language::Pl.settings(), "Settings of Aplikacji");
+    assert_eq!(Language::De.intro(), "die Typ-App welcomes you");
+
+    assert_eq!(LanguageLrc::En.lrc_about(), "About Typed App");
+    assert_eq!(LanguageLrc::De.lrc_about(), "Über der Typ-App");
+}
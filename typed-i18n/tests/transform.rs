@@ -0,0 +1,65 @@
+#![cfg(feature = "alloc")]
+use crate::derive::Language;
+use typed_i18n::{map_case, Case};
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/transform.yaml")]
+    #[typed_i18n(builder = "mixed_str")]
+    pub enum Language {
+        En,
+        De,
+        Tr,
+    }
+}
+
+#[test]
+fn parameter() {
+    assert_eq!(Language::En.greeting("istanbul"), "Hello ISTANBUL!");
+    assert_eq!(Language::Tr.greeting("istanbul"), "Merhaba İSTANBUL!");
+    assert_eq!(Language::De.greeting("straße"), "Hello STRASSE!");
+    assert_eq!(Language::En.quiet("ODYSSEUS"), "odysseus");
+    assert_eq!(Language::Tr.quiet("IRMAK"), "ırmak");
+}
+
+#[test]
+fn message() {
+    assert_eq!(Language::En.title("bob"), "Bob's settings");
+    assert_eq!(Language::De.title("bob"), "Straße von bob");
+    assert_eq!(Language::En.shout("Bob"), "WELCOME TO ISTANBUL, bob");
+    assert_eq!(Language::De.shout("Bob"), "WILLKOMMEN IN DER STRASSE, BOB");
+    assert_eq!(Language::Tr.shout("irmak"), "İSTANBUL'A HOŞ GELDİN, İRMAK");
+    // the constant text is transformed by the macro
+    let menu: &'static str = Language::Tr.menu();
+    assert_eq!(menu, "DOSYA İLE");
+    assert_eq!(Language::De.menu(), "FILE");
+    // the titlecase of ligatures and digraphs
+    assert_eq!(Language::En.heading(), "File");
+    assert_eq!(Language::De.heading(), "ǅungel");
+}
+
+#[test]
+fn runtime() {
+    assert_eq!(
+        map_case("ΟΔΟΣ ΣΟΦΙΑΣ", Case::Lower, false).collect::<String>(),
+        "οδος σοφιας"
+    );
+    assert_eq!(
+        map_case("ßtraße", Case::Capitalize, false).collect::<String>(),
+        "Sstraße"
+    );
+    assert_eq!(
+        map_case("ǆungla ﬁle", Case::Capitalize, false).collect::<String>(),
+        "ǅungla ﬁle"
+    );
+    assert_eq!(
+        map_case("ﬃ", Case::Capitalize, false).collect::<String>(),
+        "Ffi"
+    );
+    assert_eq!(map_case("İI", Case::Lower, true).collect::<String>(), "iı");
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "alloc")]
+use crate::common::{Element, Output};
+use crate::derive::Language;
+
+mod common;
+
+mod derive {
+    #![no_implicit_prelude]
+
+    use crate::common::Tester;
+    use ::std::string::String;
+    use ::typed_i18n::TypedI18N;
+
+    #[derive(Copy, Clone, TypedI18N)]
+    #[typed_i18n(filename = "tests/try_prefs.yaml")]
+    #[typed_i18n(
+        builder = "mixed_str",
+        prefix = "str_",
+        try_prefix = "try_",
+        prefs_prefix = "in_"
+    )]
+    #[typed_i18n(builder = "Tester<()>", try_prefix = "maybe_", prefs_prefix = "pick_")]
+    #[typed_i18n(builder = "_", prefix = "gen_", prefs_prefix = "from_")]
+    pub enum Language {
+        En,
+        #[typed_i18n(default = "true")]
+        De,
+        Fr,
+    }
+}
+
+#[test]
+fn try_functions() {
+    assert_eq!(Language::En.try_str_hello(), Some("Hello"));
+    assert_eq!(Language::De.try_str_bye(), Some("Tschüss"));
+    assert_eq!(Language::En.try_str_bye(), None);
+    assert_eq!(
+        Language::Fr.try_str_hello_you("Marie"),
+        Some("Bonjour Marie".to_string())
+    );
+    assert_eq!(Language::De.try_str_hello_you("Max"), None);
+
+    assert_eq!(Language::En.maybe_hello(), Some(Output::Const("Hello")));
+    assert_eq!(Language::De.maybe_hello_you("Max"), None);
+    assert_eq!(
+        Language::En.maybe_hello_you("you"),
+        Some(Output::Built(vec![
+            Element::Const("Hello "),
+            Element::String("you".to_string()),
+        ]))
+    );
+}
+
+#[test]
+fn prefs_functions() {
+    // the first preference with an own text is used
+    assert_eq!(
+        Language::in_str_hello_you(&[Language::De, Language::Fr], "Marie"),
+        "Bonjour Marie"
+    );
+    assert_eq!(
+        Language::pick_hello_you(&[Language::De, Language::En, Language::Fr], "you"),
+        Output::Built(vec![
+            Element::Const("Hello "),
+            Element::String("you".to_string()),
+        ])
+    );
+    // no preference has an own text: the first one with its fallback
+    assert_eq!(
+        Language::in_str_bye(&[Language::Fr, Language::En]),
+        "Tschüss"
+    );
+    assert_eq!(Language::in_str_hello(&[Language::Fr]), "Bonjour");
+    // no preferences at all: the default language
+    assert_eq!(Language::in_str_hello(&[]), "Hallo");
+    assert_eq!(
+        Language::from_gen_hello_you::<String>(&[Language::De, Language::Fr], "Marie"),
+        "Bonjour Marie"
+    );
+}